@@ -1,7 +1,9 @@
 //! Implements a simplified version of program argument parser.
-use std::io::{stdin, Read};
+use std::io::{stdin, Cursor, Read};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use anyhow::bail;
 use clap::Parser;
 
 /// Command line argument parser for aoc2021 solver programs
@@ -11,12 +13,40 @@ pub struct Cli {
     /// Path to an input file (or specify '-' for standard input)
     #[clap(parse(from_os_str))]
     pub input_file: Option<PathBuf>,
+    /// Runs only the specified part of the puzzle (either 1 or 2)
+    #[clap(long, parse(try_from_str = parse_part))]
+    pub part: Option<u8>,
+    /// Prints elapsed durations of each part to standard error
+    #[clap(long)]
+    pub timing: bool,
+    /// Runs each part of the puzzle this many times for rough benchmarking (at least 1)
+    #[clap(long, default_value = "1", parse(try_from_str = parse_repeat))]
+    pub repeat: usize,
+    /// In-memory input data which takes precedence over the input file when present
+    #[clap(skip)]
+    pub input_data: Option<String>,
 }
 
 impl Cli {
+    /// Creates a command line argument object whose input is fed directly from the given string,
+    /// which is mostly useful for supplying puzzle inputs in tests.
+    pub fn from_string(data: impl Into<String>) -> Self {
+        Cli {
+            input_file: None,
+            part: None,
+            timing: false,
+            repeat: 1,
+            input_data: Some(data.into()),
+        }
+    }
+
     /// Obtains a raw reader for the input file.
+    /// If in-memory input data is present, it is read through a cursor instead.
     /// If the input file is empty of '-', then standard input will be used instead.
     pub fn input_reader(&self) -> anyhow::Result<Box<dyn Read>> {
+        if let Some(data) = self.input_data.as_ref() {
+            return Ok(Box::new(Cursor::new(data.clone().into_bytes())));
+        }
         let input_file = match self.input_file.as_deref() {
             Some(s) if s == Path::new("-") => None,
             v => v,
@@ -27,4 +57,146 @@ impl Cli {
         };
         Ok(input_reader)
     }
+
+    /// Obtains the only part of the puzzle to run,
+    /// or `None` if all parts should be run.
+    pub fn part(&self) -> Option<u8> {
+        self.part
+    }
+
+    /// Obtains the number of times each part of the puzzle is run by [`Cli::time_part`].
+    pub fn repeat(&self) -> usize {
+        self.repeat
+    }
+
+    /// Runs the closure `f` as many times as [`Cli::repeat`] (but at least once)
+    /// and returns the result of the last run.
+    /// If timing is enabled, elapsed duration of the closure is also printed
+    /// to standard error in milliseconds (averaged over all runs), prefixed with the given `label`.
+    ///
+    /// Since only the closure is repeated, binaries should read and parse the input
+    /// before calling this method so that the parsed input is cached across repeats.
+    pub fn time_part<T>(&self, label: &str, mut f: impl FnMut() -> T) -> T {
+        let runs = self.repeat.max(1);
+        let start = Instant::now();
+        let mut result = f();
+        for _ in 1..runs {
+            result = f();
+        }
+        if self.timing {
+            let elapsed = start.elapsed().div_f64(runs as f64);
+            match runs {
+                1 => eprintln!("{}: {:.3} ms", label, elapsed.as_secs_f64() * 1000.0),
+                n => eprintln!(
+                    "{}: {:.3} ms (average of {} runs)",
+                    label,
+                    elapsed.as_secs_f64() * 1000.0,
+                    n
+                ),
+            }
+        }
+        result
+    }
+}
+
+/// Parses the puzzle part number from the command line argument.
+fn parse_part(s: &str) -> anyhow::Result<u8> {
+    match s.trim() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!(
+            "puzzle part must be either 1 or 2: '{}'",
+            s.escape_default()
+        ),
+    }
+}
+
+/// Parses the number of repeats from the command line argument.
+fn parse_repeat(s: &str) -> anyhow::Result<usize> {
+    match s.trim().parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => bail!(
+            "number of repeats must be a positive integer: '{}'",
+            s.escape_default()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+
+    use super::*;
+
+    #[test]
+    fn test_part() {
+        let cli = Cli::try_parse_from(["prog", "input", "--part", "2"]).unwrap();
+        assert_eq!(cli.input_file, Some(PathBuf::from("input")));
+        assert_eq!(cli.part(), Some(2));
+        let cli = Cli::try_parse_from(["prog", "input"]).unwrap();
+        assert_eq!(cli.part(), None);
+        assert!(Cli::try_parse_from(["prog", "input", "--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_repeat() {
+        let cli = Cli::try_parse_from(["prog", "input", "--repeat", "5"]).unwrap();
+        assert_eq!(cli.repeat(), 5);
+        let cli = Cli::try_parse_from(["prog", "input"]).unwrap();
+        assert_eq!(cli.repeat(), 1);
+        assert_eq!(Cli::from_string("").repeat(), 1);
+        assert!(Cli::try_parse_from(["prog", "input", "--repeat", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_time_part_repeat() {
+        let mut cli = Cli::try_parse_from(["prog", "--repeat", "5"]).unwrap();
+        let mut runs = 0;
+        assert_eq!(
+            cli.time_part("repeated", || {
+                runs += 1;
+                runs
+            }),
+            5
+        );
+        cli.timing = true;
+        assert_eq!(cli.time_part("timed", || "answer"), "answer");
+        cli.repeat = 0;
+        let mut runs = 0;
+        assert_eq!(
+            cli.time_part("zero repeats", || {
+                runs += 1;
+                runs
+            }),
+            1
+        );
+    }
+
+    #[test]
+    fn test_time_part() {
+        let mut cli = Cli::from_string("");
+        assert_eq!(cli.time_part("untimed", || 42), 42);
+        cli.timing = true;
+        assert_eq!(cli.time_part("timed", || "answer"), "answer");
+        let cli = Cli::try_parse_from(["prog", "--timing"]).unwrap();
+        assert!(cli.timing);
+    }
+
+    #[test]
+    fn test_from_string() {
+        let cli = Cli::from_string("forward 5\ndown 5\n");
+        let reader = BufReader::new(cli.input_reader().unwrap());
+        let commands: Vec<(String, i64)> = reader
+            .lines()
+            .map(|line| {
+                let line = line.unwrap();
+                let (name, value) = line.split_once(' ').unwrap();
+                (name.to_string(), value.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(
+            commands,
+            vec![("forward".to_string(), 5), ("down".to_string(), 5)]
+        );
+    }
 }
@@ -0,0 +1,51 @@
+//! Implements a single step of cellular automata over two-dimensional grids.
+use crate::grid::{DynGrid, GridPoint};
+
+/// Computes the next generation of a cellular automaton over the `grid`,
+/// where the `rule` determines the new state of each cell given its grid point
+/// and the entire current grid (so that neighboring cells can be looked up).
+/// All cells are updated simultaneously based on the current grid.
+pub fn step_automaton<T, F>(grid: &DynGrid<T>, rule: F) -> DynGrid<T>
+where
+    F: Fn(GridPoint<usize>, &DynGrid<T>) -> T,
+{
+    let (nrows, ncols) = grid.shape();
+    let rows = (0..nrows)
+        .map(|i| (0..ncols).map(|j| rule((i, j), grid)).collect())
+        .collect();
+    DynGrid::from_rows(rows).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::KingAdjacent;
+
+    /// Conway's Game of Life rule where cells beyond the grid are considered dead
+    fn game_of_life(pos: GridPoint<usize>, grid: &DynGrid<bool>) -> bool {
+        let alive_neighbors = KingAdjacent::new(pos)
+            .within_shape(grid.shape())
+            .filter(|&other_pos| grid[other_pos])
+            .count();
+        matches!((grid[pos], alive_neighbors), (true, 2) | (_, 3))
+    }
+
+    fn parse_cells(s: &str) -> DynGrid<bool> {
+        DynGrid::from_char_str(s, |c| Ok(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn blinker() {
+        let horizontal = parse_cells(".....\n.....\n.###.\n.....\n.....");
+        let vertical = parse_cells(".....\n..#..\n..#..\n..#..\n.....");
+        let next = step_automaton(&horizontal, game_of_life);
+        assert_eq!(next, vertical);
+        assert_eq!(step_automaton(&next, game_of_life), horizontal);
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = DynGrid::<bool>::from_rows(Vec::new()).unwrap();
+        assert_eq!(step_automaton(&grid, game_of_life), grid);
+    }
+}
@@ -3,10 +3,10 @@
 use std::io::{BufRead, BufReader};
 
 use clap::Parser;
-use itertools::Itertools;
 
 use aoc2021::argparser::Cli;
-use aoc2021::parsing::QuickParse;
+use aoc2021::itertools_ext::ItertoolsExt;
+use aoc2021::parsing::parse_lines;
 
 /// Main program
 fn main() {
@@ -15,22 +15,16 @@ fn main() {
     let Input { depths } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: One-point window depth increment counting
-    let p1_inc_count: usize = depths
-        .iter()
-        .tuple_windows()
-        .map(|(x, y)| (y > x) as usize)
-        .sum();
-    println!("Part 1 answer: {}", p1_inc_count);
+    if cli.part() != Some(2) {
+        let p1_inc_count = cli.time_part("Part 1", || count_window_increases(&depths, 1));
+        println!("Part 1 answer: {}", p1_inc_count);
+    }
 
     // Part 2: Three-point window depth increment counting
-    let p2_inc_count: usize = depths
-        .iter()
-        .tuple_windows()
-        .map(|(a, b, c)| a + b + c)
-        .tuple_windows()
-        .map(|(x, y)| (y > x) as usize)
-        .sum();
-    println!("Part 2 answer: {}", p2_inc_count);
+    if cli.part() != Some(1) {
+        let p2_inc_count = cli.time_part("Part 2", || count_window_increases(&depths, 3));
+        println!("Part 2 answer: {}", p2_inc_count);
+    }
 }
 
 /// Program input data
@@ -43,10 +37,39 @@ struct Input {
 impl Input {
     /// Parses program input from buffered reader.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
-        let mut depths = Vec::new();
-        for line in reader.lines() {
-            depths.push(line?.trim().quickparse()?);
-        }
+        let depths = parse_lines(reader)?;
         Ok(Input { depths })
     }
 }
+
+/// Counts the number of times the sum of a sliding window of size `window`
+/// is strictly greater than the sum of the preceding window.
+/// Inputs with fewer than `window + 1` depths have no such increases.
+fn count_window_increases(depths: &[i64], window: usize) -> usize {
+    depths
+        .iter()
+        .copied()
+        .windowed_sums(window)
+        .count_increases()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day01_sample.txt");
+
+    #[test_case(1, 7; "single depth")]
+    #[test_case(3, 5; "three depths")]
+    fn count_window_increases_sample(window: usize, expected: usize) {
+        let Input { depths } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(count_window_increases(&depths, window), expected);
+    }
+
+    #[test]
+    fn count_window_increases_too_short() {
+        assert_eq!(count_window_increases(&[1, 2], 3), 0);
+    }
+}
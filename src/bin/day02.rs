@@ -3,11 +3,11 @@
 use std::io::{BufRead, BufReader};
 use std::str::FromStr;
 
-use anyhow::bail;
+use anyhow::{bail, ensure, Context};
 use clap::Parser;
 
 use aoc2021::argparser::Cli;
-use aoc2021::parsing::QuickParse;
+use aoc2021::parsing::{parse_lines, QuickParse};
 
 /// Main program
 fn main() {
@@ -16,18 +16,17 @@ fn main() {
     let Input { commands } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Naïve submarine navigation
-    let p1_submarine = commands.iter().fold(SubmarinePos::default(), |pos, cmd| {
-        next_submarine_pos(&pos, cmd)
-    });
-    println!("Part 1 answer: {}", p1_submarine.pos_product());
+    if cli.part() != Some(2) {
+        let p1_submarine: SubmarinePos = cli.time_part("Part 1", || navigate(commands.as_slice()));
+        println!("Part 1 answer: {}", p1_submarine.pos_product());
+    }
 
     // Part 2: Submarine navigation with aim attribute
-    let p2_submarine = commands
-        .iter()
-        .fold(SubmarineStatus::default(), |status, cmd| {
-            next_submarine_status(&status, cmd)
-        });
-    println!("Part 2 answer: {}", p2_submarine.pos.pos_product());
+    if cli.part() != Some(1) {
+        let p2_submarine: SubmarineStatus =
+            cli.time_part("Part 2", || navigate(commands.as_slice()));
+        println!("Part 2 answer: {}", p2_submarine.pos.pos_product());
+    }
 }
 
 /// Program input data
@@ -40,10 +39,7 @@ struct Input {
 impl Input {
     /// Parses program input from buffered reader.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
-        let mut commands = Vec::new();
-        for line in reader.lines() {
-            commands.push(line?.quickparse()?);
-        }
+        let commands = parse_lines(reader)?;
         Ok(Input { commands })
     }
 }
@@ -56,15 +52,32 @@ enum Command {
     Up(i64),
 }
 
+impl Command {
+    /// Parses the distance parameter of the command with the given `verb`.
+    /// Distances must be non-negative since the direction is already implied by the verb.
+    fn parse_param(verb: &str, param: &str) -> anyhow::Result<i64> {
+        let dist: i64 = param
+            .quickparse()
+            .with_context(|| format!("invalid parameter for command '{}'", verb))?;
+        ensure!(
+            dist >= 0,
+            "negative parameter for command '{}': {}",
+            verb,
+            dist
+        );
+        Ok(dist)
+    }
+}
+
 impl FromStr for Command {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let tokens: Vec<&str> = s.split_ascii_whitespace().collect();
         let cmd = match tokens[..] {
-            ["forward", param] => Command::Forward(param.parse()?),
-            ["down", param] => Command::Down(param.parse()?),
-            ["up", param] => Command::Up(param.parse()?),
+            ["forward", param] => Command::Forward(Command::parse_param("forward", param)?),
+            ["down", param] => Command::Down(Command::parse_param("down", param)?),
+            ["up", param] => Command::Up(Command::parse_param("up", param)?),
             [] => bail!("empty command"),
             _ => bail!("invalid command: {}", s.trim()),
         };
@@ -97,41 +110,98 @@ struct SubmarineStatus {
     aim: i64,
 }
 
-/// Computes next submarine position based on naïve understanding.
-fn next_submarine_pos(pos: &SubmarinePos, cmd: &Command) -> SubmarinePos {
-    match cmd {
-        Command::Forward(dist) => SubmarinePos {
-            x: pos.x + dist,
-            y: pos.y,
-        },
-        Command::Down(dist) => SubmarinePos {
-            x: pos.x,
-            y: pos.y + dist,
-        },
-        Command::Up(dist) => SubmarinePos {
-            x: pos.x,
-            y: pos.y - dist,
-        },
+/// Submarine navigation model which interprets commands in its own way
+trait Navigate {
+    /// Updates the submarine in place according to the command.
+    fn apply(&mut self, cmd: &Command);
+}
+
+/// Naïve understanding of submarine commands
+impl Navigate for SubmarinePos {
+    fn apply(&mut self, cmd: &Command) {
+        match cmd {
+            Command::Forward(dist) => self.x += dist,
+            Command::Down(dist) => self.y += dist,
+            Command::Up(dist) => self.y -= dist,
+        }
     }
 }
 
-/// Computes next submarine status based on correct understanding.
-fn next_submarine_status(status: &SubmarineStatus, cmd: &Command) -> SubmarineStatus {
-    match cmd {
-        Command::Forward(dist) => SubmarineStatus {
-            pos: SubmarinePos {
-                x: status.pos.x + dist,
-                y: status.pos.y + status.aim * dist,
-            },
-            aim: status.aim,
-        },
-        Command::Down(dist) => SubmarineStatus {
-            pos: status.pos,
-            aim: status.aim + dist,
-        },
-        Command::Up(dist) => SubmarineStatus {
-            pos: status.pos,
-            aim: status.aim - dist,
-        },
+/// Correct understanding of submarine commands
+impl Navigate for SubmarineStatus {
+    fn apply(&mut self, cmd: &Command) {
+        match cmd {
+            Command::Forward(dist) => {
+                self.pos.x += dist;
+                self.pos.y += self.aim * dist;
+            }
+            Command::Down(dist) => self.aim += dist,
+            Command::Up(dist) => self.aim -= dist,
+        }
+    }
+}
+
+/// Navigates the submarine from its default state through all commands.
+fn navigate<N>(commands: &[Command]) -> N
+where
+    N: Navigate + Default,
+{
+    commands.iter().fold(N::default(), |mut submarine, cmd| {
+        submarine.apply(cmd);
+        submarine
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day02_sample.txt");
+
+    #[test]
+    fn navigate_naive_sample() {
+        let Input { commands } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let submarine: SubmarinePos = navigate(&commands);
+        assert_eq!(submarine, SubmarinePos { x: 15, y: 10 });
+        assert_eq!(submarine.pos_product(), 150);
+    }
+
+    #[test]
+    fn navigate_with_aim_sample() {
+        let Input { commands } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let submarine: SubmarineStatus = navigate(&commands);
+        assert_eq!(submarine.pos, SubmarinePos { x: 15, y: 60 });
+        assert_eq!(submarine.aim, 10);
+        assert_eq!(submarine.pos.pos_product(), 900);
+    }
+
+    #[test]
+    fn parse_valid_commands() {
+        assert_eq!("forward 5".parse::<Command>().unwrap(), Command::Forward(5));
+        assert_eq!("  down 0 ".parse::<Command>().unwrap(), Command::Down(0));
+        assert_eq!("up 12".parse::<Command>().unwrap(), Command::Up(12));
+    }
+
+    #[test]
+    fn parse_negative_parameter() {
+        let err = "down -3".parse::<Command>().unwrap_err();
+        assert_eq!(err.to_string(), "negative parameter for command 'down': -3");
+    }
+
+    #[test]
+    fn parse_malformed_parameter() {
+        let err = "forward x5".parse::<Command>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid parameter for command 'forward'");
+        assert_eq!(err.chain().count(), 2);
+    }
+
+    #[test]
+    fn parse_invalid_commands() {
+        let err = "".parse::<Command>().unwrap_err();
+        assert_eq!(err.to_string(), "empty command");
+        let err = "backward 3".parse::<Command>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid command: backward 3");
+        let err = "up 1 2".parse::<Command>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid command: up 1 2");
     }
 }
@@ -1,15 +1,14 @@
 //! Day 3: Binary Diagnostic, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/3>
-use std::fmt::{Display, Formatter};
 use std::io::{BufRead, BufReader};
-use std::ops::{Deref, Not};
-use std::str::FromStr;
+use std::ops::Not;
 
 use anyhow::Context;
 use clap::Parser;
 use itertools::Itertools;
 
 use aoc2021::argparser::Cli;
+use aoc2021::bit_vec::BitVec;
 use aoc2021::parsing::QuickParse;
 
 /// Main program
@@ -22,14 +21,22 @@ fn main() {
     let bit_vector_refs: Vec<_> = bit_vectors.iter().collect();
 
     // Part 1: Power consumption computation
-    let p1_answer = compute_power_consumption(bit_vector_refs.as_slice())
-        .expect("error while computing power consumption");
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            compute_power_consumption(bit_vector_refs.as_slice())
+                .expect("error while computing power consumption")
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Life support rating computation
-    let p2_answer = compute_life_support_rating(bit_vector_refs.as_slice())
-        .expect("error while computing life support rating");
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            compute_life_support_rating(bit_vector_refs.as_slice())
+                .expect("error while computing life support rating")
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -50,74 +57,6 @@ impl Input {
     }
 }
 
-/// Bit vector wrapper over a vector of boolean
-///
-/// # Implementation Note
-/// This approach wastes significant amount of memory,
-/// due to 8 bit being used to store a single boolean.
-/// - TODO: Use [`bitvec::BitVec`] from external crate instead
-///
-/// [`bitvec::BitVec`]: https://docs.rs/bitvec/latest/bitvec/vec/struct.BitVec.html
-#[derive(Debug, Clone)]
-struct BitVec(Vec<bool>);
-
-// NOTE: I cannot figure out how to get `impl From<_> for T` to work
-// for generic T: num::PrimInt + num::Unsigned. So using macros for now.
-macro_rules! impl_from_bitvec_for_int {
-    ($($t:ty),*) => {$(
-        impl From<&BitVec> for $t {
-            fn from(num: &BitVec) -> Self {
-                <$t>::from_str_radix(num.to_string().as_str(), 2).unwrap()
-            }
-        }
-    )*};
-    ($($t:ty,)*) => {
-        impl_from_bitvec_for_int!( $($t:ty),* )
-    };
-}
-impl_from_bitvec_for_int![usize, u8, u16, u32, u64, u128];
-
-impl Display for BitVec {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s: String = self
-            .0
-            .iter()
-            .copied()
-            .map(|b| if b { '1' } else { '0' })
-            .collect();
-        write!(f, "{}", s)
-    }
-}
-
-impl FromStr for BitVec {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut inner = Vec::new();
-        for c in s.trim().chars() {
-            let d = c.to_digit(2).with_context(|| {
-                format!("invalid character in bit string: '{}'", c.escape_default())
-            })?;
-            inner.push(d != 0);
-        }
-        Ok(BitVec(inner))
-    }
-}
-
-impl FromIterator<bool> for BitVec {
-    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
-        BitVec(iter.into_iter().collect())
-    }
-}
-
-impl Deref for BitVec {
-    type Target = Vec<bool>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
 /// Computes the power consumption, which is the product of these two factors:
 /// -  `gamma` = radix majority voting of bit vector data
 /// -  `epsilon` = radix minority voting of the bit vector data
@@ -153,19 +92,16 @@ fn eliminate_until_last<'a, F>(
 where
     F: Fn(&[&BitVec], usize) -> anyhow::Result<bool>,
 {
-    let mut survivors: Vec<_> = numbers.iter().copied().collect();
+    let mut survivors = numbers.to_vec();
     for index in 0_usize.. {
         if survivors.len() <= 1 {
             return survivors
-                .get(0)
+                .first()
                 .copied()
                 .context("empty collection of bit vectors");
         }
         let vote_result = vote_criterion(survivors.as_slice(), index)?;
-        survivors = survivors
-            .into_iter()
-            .filter(|num| num[index] == vote_result)
-            .collect();
+        survivors.retain(|num| num[index] == vote_result);
     }
     unreachable!()
 }
@@ -2,21 +2,19 @@
 //! <https://adventofcode.com/2021/day/4>
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::io;
 use std::io::{BufRead, BufReader};
 use std::iter::Sum;
 use std::str::FromStr;
 
-use anyhow::{anyhow, Context};
+use anyhow::{bail, ensure, Context};
 use clap::Parser;
-use itertools::Itertools;
 use num::PrimInt;
 
 use aoc2021::argparser::Cli;
 use aoc2021::collect_array::CollectArray;
 use aoc2021::grid::GridIndices;
 use aoc2021::hashing::HashMap;
-use aoc2021::parsing::QuickParse;
+use aoc2021::parsing::{parse_blocks, QuickParse};
 
 /// Main program
 fn main() {
@@ -29,24 +27,25 @@ fn main() {
     if boards.is_empty() {
         panic!("there is not even a single bingo board read from input");
     }
-    let play_results: Vec<_> = boards
-        .iter()
-        .map(|board| board.play_with_lots(lots.as_slice()))
-        .collect();
+    let play_results = play_all(boards.as_slice(), lots.as_slice());
 
     // Part 1: First bingo board to win
-    let p1_first_win_score = {
-        let result = play_results.iter().min_by_key(|r| r.rounds_played).unwrap();
-        result.score.expect("unfinished board; score unavailable")
-    };
-    println!("Part 1 answer: {}", p1_first_win_score);
+    if cli.part() != Some(2) {
+        let p1_first_win_score = cli.time_part("Part 1", || {
+            let result = play_results.iter().min_by_key(|r| r.rounds_played).unwrap();
+            result.score.expect("unfinished board; score unavailable")
+        });
+        println!("Part 1 answer: {}", p1_first_win_score);
+    }
 
     // Part 2: Last bingo board to win
-    let p2_last_win_score = {
-        let result = play_results.iter().max_by_key(|r| r.rounds_played).unwrap();
-        result.score.expect("unfinished board; score unavailable")
-    };
-    println!("Part 2 answer: {}", p2_last_win_score);
+    if cli.part() != Some(1) {
+        let p2_last_win_score = cli.time_part("Part 2", || {
+            let result = play_results.iter().max_by_key(|r| r.rounds_played).unwrap();
+            result.score.expect("unfinished board; score unavailable")
+        });
+        println!("Part 2 answer: {}", p2_last_win_score);
+    }
 }
 
 /// Program input data
@@ -60,53 +59,29 @@ struct Input {
 
 impl Input {
     /// Parses program input from buffered reader.
+    /// The sequence of lots is the first block of lines, which may wrap across several lines,
+    /// and each subsequent block separated by blank lines is a bingo board.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
-        let mut batches = reader.lines().batching(collect_line_batch);
-
+        let mut blocks = parse_blocks(reader, Ok)?.into_iter();
+        let lots_lines = blocks.next().context("missing lots data")?;
         let mut lots = Vec::new();
-        let batch = batches.next().context("missing lots data")??;
-        for line in batch {
-            for token in line.split(',') {
+        for line in lots_lines {
+            for token in line.trim().trim_end_matches(',').split(',') {
                 lots.push(token.trim().quickparse()?);
             }
         }
 
         let mut boards = Vec::new();
-        for batch in batches {
-            boards.push(Board::from_lines(batch?)?);
+        for (i, block) in blocks.enumerate() {
+            let board = Board::from_lines(block)
+                .with_context(|| format!("cannot parse bingo board {}", i + 1))?;
+            boards.push(board);
         }
 
         Ok(Input { lots, boards })
     }
 }
 
-/// Collects strings from an iterator into a vector until a seemingly empty string
-/// (which includes strings containing just whitespaces) has been found.
-/// Empty strings will not be included as part of the returned vector.
-/// If the iterator has already been exhausted in the first place, `None` is returned.
-fn collect_line_batch<I>(it: &mut I) -> Option<anyhow::Result<Vec<String>>>
-where
-    I: Iterator<Item = Result<String, io::Error>>,
-{
-    let mut buffer = Vec::new();
-    for line in it {
-        match line {
-            Ok(s) if s.trim().is_empty() => return Some(Ok(buffer)),
-            Ok(s) => buffer.push(s),
-            Err(_) => {
-                return Some(Err(anyhow!(
-                    "error while reading a line of string from input"
-                )))
-            }
-        }
-    }
-    if buffer.is_empty() {
-        None
-    } else {
-        Some(Ok(buffer))
-    }
-}
-
 /// Bingo board with compile-time constant size and flexible element type.
 /// Parameters `R` and `C` are the number of rows and columns, respectively.
 #[derive(Debug, Clone)]
@@ -126,20 +101,25 @@ where
 {
     /// Constructs a bingo board from 2-d array grid of numbers.
     /// Member `mapper` will be constructed on-the-fly.
-    fn new(numbers: [[T; C]; R]) -> Self
+    /// Numbers appearing more than once on the board are rejected with an error.
+    fn new(numbers: [[T; C]; R]) -> anyhow::Result<Self>
     where
-        T: Hash,
+        T: Debug + Hash,
     {
-        let mapper: HashMap<T, (usize, usize)> = numbers
-            .into_iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.into_iter()
-                    .enumerate()
-                    .map(move |(j, value)| (value, (i, j)))
-            })
-            .collect();
-        Board { numbers, mapper }
+        let mut mapper: HashMap<T, (usize, usize)> = HashMap::default();
+        for (i, row) in numbers.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                if let Some(prev_pos) = mapper.insert(value, (i, j)) {
+                    bail!(
+                        "duplicated number {:?} on bingo board at {:?} and {:?}",
+                        value,
+                        prev_pos,
+                        (i, j)
+                    );
+                }
+            }
+        }
+        Ok(Board { numbers, mapper })
     }
 
     /// Constructs a bingo board from a vector of strings
@@ -159,8 +139,10 @@ where
         Board::try_from(board_numbers)
     }
 
-    /// Plays the bingo board from the beginning with the given sequence of lots,
+    /// Plays the bingo board alone from the beginning with the given sequence of lots,
     /// and returns the final score and the number of rounds played.
+    /// This serves as the reference replay for [`play_all`] in tests.
+    #[cfg(test)]
     fn play_with_lots(&self, lots: &[T]) -> PlayResult<T>
     where
         T: Hash + Sum,
@@ -182,7 +164,7 @@ where
     }
 
     /// Spawns a new bingo board checker of the current board.
-    fn spawn_checker(&self) -> BoardChecker<T, R, C> {
+    fn spawn_checker(&self) -> BoardChecker<'_, T, R, C> {
         BoardChecker {
             board: self,
             marks: [[false; C]; R],
@@ -198,13 +180,59 @@ where
     type Error = anyhow::Error;
 
     fn try_from(numbers: Vec<Vec<T>>) -> Result<Self, Self::Error> {
-        let mut rows = Vec::with_capacity(R);
-        for row in numbers {
-            let row: [_; C] = row.into_iter().collect_exact()?;
-            rows.push(row);
+        for (i, row) in numbers.iter().enumerate() {
+            ensure!(
+                row.len() == C,
+                "bingo row {} has {} numbers but expected {}",
+                i,
+                row.len(),
+                C
+            );
+        }
+        Board::new(numbers.into_iter().flatten().collect_exact_grid()?)
+    }
+}
+
+/// Plays all bingo boards simultaneously from the beginning with the given sequence of lots,
+/// drawing each lot only once and marking it on all boards that have not yet won.
+/// Returns the final score and the number of rounds played for each board in the same order.
+fn play_all<T, const R: usize, const C: usize>(
+    boards: &[Board<T, R, C>],
+    lots: &[T],
+) -> Vec<PlayResult<T>>
+where
+    T: PrimInt + Hash + Sum,
+{
+    let mut checkers: Vec<_> = boards.iter().map(|board| board.spawn_checker()).collect();
+    let mut results: Vec<Option<PlayResult<T>>> = vec![None; boards.len()];
+    let mut unfinished_count = boards.len();
+    for (i, lot) in lots.iter().copied().enumerate() {
+        if unfinished_count == 0 {
+            break;
+        }
+        for (checker, result) in checkers.iter_mut().zip(results.iter_mut()) {
+            if result.is_some() {
+                continue;
+            }
+            let score = checker.mark(lot);
+            if score.is_some() {
+                *result = Some(PlayResult {
+                    score,
+                    rounds_played: i,
+                });
+                unfinished_count -= 1;
+            }
         }
-        Ok(Board::new(rows.into_iter().collect_exact()?))
     }
+    results
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or(PlayResult {
+                score: None,
+                rounds_played: lots.len(),
+            })
+        })
+        .collect()
 }
 
 /// Bingo board checker which optimizes for bingo checking
@@ -265,7 +293,7 @@ where
 }
 
 /// The result from playing a bingo game with a sequence of lots
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct PlayResult<T> {
     /// Final score of the bingo board; `None` if the board has reached the winning state
     score: Option<T>,
@@ -273,3 +301,54 @@ struct PlayResult<T> {
     /// If the winning state has never been reached, it still stores the total number of lots called.
     rounds_played: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../inputs/day04_sample.txt");
+
+    #[test]
+    fn play_all_same_as_replay() {
+        let Input { lots, boards } = Input::from_buffer(SAMPLE_INPUT.as_bytes()).unwrap();
+        let results = play_all(&boards, &lots);
+        let replayed: Vec<_> = boards.iter().map(|b| b.play_with_lots(&lots)).collect();
+        assert_eq!(results, replayed);
+        assert_eq!(results[2].score, Some(4512));
+        assert_eq!(results[1].score, Some(1924));
+    }
+
+    #[test]
+    fn new_rejects_duplicates() {
+        let err = Board::<i64, 2, 3>::new([[1, 2, 3], [4, 2, 6]]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "duplicated number 2 on bingo board at (0, 1) and (1, 1)"
+        );
+        let lines = vec!["1 2".to_string(), "3 1".to_string()];
+        assert!(Board::<i64, 2, 2>::from_lines(lines).is_err());
+    }
+
+    #[test]
+    fn new_accepts_distinct() {
+        let board = Board::<i64, 2, 3>::new([[1, 2, 3], [4, 5, 6]]).unwrap();
+        assert_eq!(board.mapper.len(), 6);
+        assert_eq!(board.mapper[&5], (1, 1));
+    }
+
+    #[test]
+    fn lots_wrapping_across_lines() {
+        let input = "7,4,9,\n5,11\n17,23\n\n";
+        let board = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n";
+        let input = format!("{}{}", input, board);
+        let Input { lots, boards } = Input::from_buffer(input.as_bytes()).unwrap();
+        assert_eq!(lots, vec![7, 4, 9, 5, 11, 17, 23]);
+        assert_eq!(boards.len(), 1);
+    }
+
+    #[test]
+    fn missing_lots() {
+        let err = Input::from_buffer("\n\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "missing lots data");
+    }
+}
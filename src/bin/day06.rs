@@ -7,6 +7,7 @@ use clap::Parser;
 use nalgebra::{matrix, SVector};
 
 use aoc2021::argparser::Cli;
+use aoc2021::linalg::matrix_pow;
 use aoc2021::parsing::QuickParse;
 
 /// Main program
@@ -32,20 +33,22 @@ fn main() {
     ];
 
     // Part 1: fish counting after 80 days
-    let p1_total_fish = {
-        let fish_counts = (0..80).fold(init_counts, |v, _| trans_mat * v);
-        fish_counts.sum()
-    };
-    println!("Part 1 answer: {}", p1_total_fish);
+    if cli.part() != Some(2) {
+        let p1_total_fish = cli.time_part("Part 1", || {
+            let fish_counts = matrix_pow(trans_mat, 80) * init_counts;
+            fish_counts.sum()
+        });
+        println!("Part 1 answer: {}", p1_total_fish);
+    }
 
     // Part 2: fish counting after 256 days
-    // NOTE: I could have used repeated squaring exponentiation method to reduce some time
-    // if the number of days happened to be much larger than this.
-    let p2_total_fish = {
-        let fish_counts = (0..256).fold(init_counts, |v, _| trans_mat * v);
-        fish_counts.sum()
-    };
-    println!("Part 2 answer: {}", p2_total_fish);
+    if cli.part() != Some(1) {
+        let p2_total_fish = cli.time_part("Part 2", || {
+            let fish_counts = matrix_pow(trans_mat, 256) * init_counts;
+            fish_counts.sum()
+        });
+        println!("Part 2 answer: {}", p2_total_fish);
+    }
 }
 
 /// Program input data
@@ -1,8 +1,10 @@
 //! Day 7: The Treachery of Whales, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/7>
+use std::cmp::Ordering;
 use std::io::{BufRead, BufReader};
 
 use clap::Parser;
+use itertools::Itertools;
 
 use aoc2021::argparser::Cli;
 use aoc2021::parsing::QuickParse;
@@ -11,43 +13,27 @@ use aoc2021::parsing::QuickParse;
 fn main() {
     let cli = Cli::parse();
     let input_reader = BufReader::new(cli.input_reader().expect("cannot open file"));
-    let Input { mut positions } = Input::from_buffer(input_reader).expect("cannot parse input");
+    let Input { positions } = Input::from_buffer(input_reader).expect("cannot parse input");
 
-    // The rest of the code assumes that all positions are sorted.
-    positions.sort_unstable();
-
-    // Part 1: Fuels from distance according to linear function (at right-biased median point)
-    let p1_fuels: i64 = {
-        let median = positions[positions.len() / 2];
-        positions
-            .iter()
-            .copied()
-            .map(|pos| const_per_unit_dist_fuel(pos, median))
-            .sum()
-    };
-    println!("Part 1 answer: {}", p1_fuels);
+    // Part 1: Fuels from distance according to linear function,
+    // i.e. using one fuel per distance unit
+    if cli.part() != Some(2) {
+        let const_per_unit_dist_fuel = |dist: i64| dist;
+        let (_, p1_fuels) = cli.time_part("Part 1", || {
+            optimal_fuel(positions.as_slice(), const_per_unit_dist_fuel)
+        });
+        println!("Part 1 answer: {}", p1_fuels);
+    }
 
-    // Part 2: Fuels from distance according to triangle shape accumulation
-    // NOTE: To be honest, I don't really know if checking only the neighboring values
-    // of the mean position as the candidate positions are sufficient to find the optimal answer.
-    // A few other potential alternative solutions (must validate assumptions first):
-    // -  Using binary search, assuming that the fuel function is a unimodal function
-    // -  Using golden-section search, assuming that the fuel function is a convex function
-    let p2_fuels: i64 = {
-        let mean = positions.iter().sum::<i64>() as f64 / positions.len() as f64;
-        [mean.floor() as i64, mean.ceil() as i64]
-            .into_iter()
-            .map(|mean| {
-                positions
-                    .iter()
-                    .copied()
-                    .map(|pos| linear_per_unit_dist_fuel(pos, mean))
-                    .sum()
-            })
-            .min()
-            .unwrap()
-    };
-    println!("Part 2 answer: {}", p2_fuels);
+    // Part 2: Fuels from distance according to triangle shape accumulation,
+    // i.e. using linearly increasing amount of fuel for each extra unit of distance traveled
+    if cli.part() != Some(1) {
+        let linear_per_unit_dist_fuel = |dist: i64| dist * (dist + 1) / 2;
+        let (_, p2_fuels) = cli.time_part("Part 2", || {
+            optimal_fuel(positions.as_slice(), linear_per_unit_dist_fuel)
+        });
+        println!("Part 2 answer: {}", p2_fuels);
+    }
 }
 
 /// Program input data
@@ -70,15 +56,94 @@ impl Input {
     }
 }
 
-/// Fuels required when using one fuel per distance unit.
-fn const_per_unit_dist_fuel(p: i64, q: i64) -> i64 {
-    (p - q).abs()
+/// Finds the target position which minimizes the total fuels required for all crabs to move there,
+/// where `cost` computes the fuels required for a single crab to travel the given distance.
+/// Returns the optimal target position together with its total fuels
+/// (or zeros if there are no crabs at all).
+///
+/// The total fuel function is assumed to be convex over the target positions
+/// (which holds when `cost` is convex and non-decreasing over non-negative distances),
+/// so ternary search over the range of crab positions is sufficient to find the optimum.
+fn optimal_fuel<F>(positions: &[i64], cost: F) -> (i64, i64)
+where
+    F: Fn(i64) -> i64,
+{
+    let (mut lo, mut hi) = match positions.iter().copied().minmax().into_option() {
+        Some(bounds) => bounds,
+        None => return (0, 0),
+    };
+    while hi - lo > 2 {
+        let third = (hi - lo) / 3;
+        let (mid_lo, mid_hi) = (lo + third, hi - third);
+        match total_fuel(positions, mid_lo, &cost).cmp(&total_fuel(positions, mid_hi, &cost)) {
+            Ordering::Less => hi = mid_hi - 1,
+            Ordering::Greater => lo = mid_lo + 1,
+            Ordering::Equal => {
+                lo = mid_lo;
+                hi = mid_hi;
+            }
+        }
+    }
+    (lo..=hi)
+        .map(|target| (target, total_fuel(positions, target, &cost)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap()
+}
+
+/// Computes the total fuels required for all crabs to move to the `target` position,
+/// where `unit_cost` computes the fuels required for a single crab to travel the given distance.
+fn total_fuel(positions: &[i64], target: i64, unit_cost: impl Fn(i64) -> i64) -> i64 {
+    positions
+        .iter()
+        .map(|pos| unit_cost((pos - target).abs()))
+        .sum()
 }
 
-/// Fuels required when using linearly increasing amount of fuel
-/// for each extra unit of distance traveled,
-/// ended up accumulating to a triangle number in terms of the distances apart.
-fn linear_per_unit_dist_fuel(p: i64, q: i64) -> i64 {
-    let dist = (p - q).abs();
-    dist * (dist + 1) / 2
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day07_sample.txt");
+
+    fn brute_force(positions: &[i64], cost: impl Fn(i64) -> i64) -> i64 {
+        let (lo, hi) = positions.iter().copied().minmax().into_option().unwrap();
+        (lo..=hi)
+            .map(|target| total_fuel(positions, target, &cost))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn ternary_search_same_as_brute_force() {
+        let Input { positions } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let linear = |dist: i64| dist;
+        let triangle = |dist: i64| dist * (dist + 1) / 2;
+        assert_eq!(optimal_fuel(&positions, linear), (2, 37));
+        assert_eq!(optimal_fuel(&positions, triangle), (5, 168));
+        for positions in [positions, vec![3], vec![0, 100], vec![7, 7, 7, 1, 50, 49]] {
+            assert_eq!(
+                optimal_fuel(&positions, linear).1,
+                brute_force(&positions, linear)
+            );
+            assert_eq!(
+                optimal_fuel(&positions, triangle).1,
+                brute_force(&positions, triangle)
+            );
+        }
+    }
+
+    #[test]
+    fn total_fuel_with_cost_closures() {
+        let Input { positions } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(total_fuel(&positions, 1, |dist| dist), 41);
+        assert_eq!(total_fuel(&positions, 3, |dist| dist), 39);
+        assert_eq!(total_fuel(&positions, 10, |dist| dist), 71);
+        assert_eq!(total_fuel(&positions, 2, |dist| dist * (dist + 1) / 2), 206);
+        assert_eq!(total_fuel(&positions, 0, |_| 1), positions.len() as i64);
+    }
+
+    #[test]
+    fn no_crabs() {
+        assert_eq!(optimal_fuel(&[], |dist| dist), (0, 0));
+    }
 }
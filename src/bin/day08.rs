@@ -11,7 +11,6 @@ use regex::Regex;
 
 use aoc2021::argparser::Cli;
 use aoc2021::collect_array::CollectArray;
-use aoc2021::parsing::QuickParse;
 
 /// Hand-crafted information to decode toggle patterns into actual integer digits.
 /// In general, it performs an XOR-bitmask-then-count-one-bits test with each pattern.
@@ -43,22 +42,28 @@ fn main() {
     let Input { display_logs } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Counting appearances of displaying digits with unique number of segments
-    let p1_answer: usize = display_logs
-        .iter()
-        .map(DisplayLog::count_quickly_decodable_display_patterns)
-        .sum();
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer: usize = cli.time_part("Part 1", || {
+            display_logs
+                .iter()
+                .map(DisplayLog::count_quickly_decodable_display_patterns)
+                .sum()
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Decoding four-digit displaying numbers and add them up
-    let p2_answer: u64 = {
-        let numbers: Vec<_> = display_logs
-            .iter()
-            .map(DisplayLog::decode_display_patterns)
-            .try_collect()
-            .expect("error occurred while decoding display patterns");
-        numbers.into_iter().sum()
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer: u64 = cli.time_part("Part 2", || {
+            let numbers: Vec<_> = display_logs
+                .iter()
+                .map(DisplayLog::decode_display_patterns)
+                .try_collect()
+                .expect("error occurred while decoding display patterns");
+            numbers.into_iter().sum()
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -73,7 +78,7 @@ impl Input {
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
         let mut display_logs = Vec::new();
         for line in reader.lines() {
-            display_logs.push(line?.quickparse()?);
+            display_logs.push(line?.parse()?);
         }
         Ok(Input { display_logs })
     }
@@ -90,11 +95,12 @@ struct DisplayLog {
 
 impl DisplayLog {
     /// Constructs a new [`DisplayLog`] but with `signal_patterns` properly sorted.
-    fn new(digit_patterns: [u8; 10], display_patterns: [u8; 4]) -> Self {
-        DisplayLog {
-            digit_patterns: sort_toggle_patterns(&digit_patterns),
+    /// An error is returned if the digit patterns cannot be unambiguously sorted.
+    fn new(digit_patterns: [u8; 10], display_patterns: [u8; 4]) -> anyhow::Result<Self> {
+        Ok(DisplayLog {
+            digit_patterns: sort_toggle_patterns(&digit_patterns)?,
             display_patterns,
-        }
+        })
     }
 
     /// Decodes the toggle `pattern` into an integer digit.
@@ -165,10 +171,10 @@ impl FromStr for DisplayLog {
         let all_patterns: Vec<_> = (1..=14)
             .map(|i| pattern_from_scribbles(&captures[i]))
             .try_collect()?;
-        let digit_patterns = (&all_patterns[0..10]).iter().copied().collect_exact()?;
-        let display_patterns = (&all_patterns[10..14]).iter().copied().collect_exact()?;
+        let digit_patterns = all_patterns[0..10].iter().copied().collect_exact()?;
+        let display_patterns = all_patterns[10..14].iter().copied().collect_exact()?;
 
-        Ok(DisplayLog::new(digit_patterns, display_patterns))
+        DisplayLog::new(digit_patterns, display_patterns)
     }
 }
 
@@ -189,28 +195,78 @@ fn pattern_from_scribbles<T: AsRef<str>>(scribbles: T) -> anyhow::Result<u8> {
 }
 
 /// Sorts the toggle patterns so that the `i`-th pattern precisely decodes to digit `i`.
-fn sort_toggle_patterns(patterns: &[u8; 10]) -> [u8; 10] {
-    let one_decoder = DECODER_BY_NULL_ONE_FOUR[1].0;
-    let one_mask = pattern_by_xor_mask_tests(patterns, [(0, one_decoder)].as_slice());
-    let four_decoder = DECODER_BY_NULL_ONE_FOUR[4].0;
-    let four_mask = pattern_by_xor_mask_tests(patterns, [(0, four_decoder)].as_slice());
-    DECODER_BY_NULL_ONE_FOUR.map(|(null, one, four)| {
-        let tests = [(0, null), (one_mask, one), (four_mask, four)];
-        pattern_by_xor_mask_tests(patterns, tests.as_slice())
-    })
+/// An error is returned if the pattern of some digit cannot be uniquely identified.
+fn sort_toggle_patterns(patterns: &[u8; 10]) -> anyhow::Result<[u8; 10]> {
+    let resolve_digit = |digit: usize, tests: &[(u8, u32)]| {
+        pattern_by_xor_mask_tests(patterns, tests)
+            .with_context(|| format!("cannot uniquely resolve the pattern for digit {}", digit))
+    };
+    let one_mask = resolve_digit(1, [(0, DECODER_BY_NULL_ONE_FOUR[1].0)].as_slice())?;
+    let four_mask = resolve_digit(4, [(0, DECODER_BY_NULL_ONE_FOUR[4].0)].as_slice())?;
+    DECODER_BY_NULL_ONE_FOUR
+        .iter()
+        .enumerate()
+        .map(|(digit, &(null, one, four))| {
+            let tests = [(0, null), (one_mask, one), (four_mask, four)];
+            resolve_digit(digit, tests.as_slice())
+        })
+        .try_collect_exact()
 }
 
-/// Finds the first (and hopefully the only) toggle pattern
-/// that satisfies all of XOR-bitmask-then-count-one-bits tests provided.
+/// Finds the only toggle pattern that satisfies all of XOR-bitmask-then-count-one-bits tests provided.
+/// An error is returned if there is no such pattern or more than one.
 ///
 /// Each test consists of `(bit_mask, one_bits)`:
 /// -  `bit_mask`: XOR bit mask which must be applied to a toggle pattern in question first
 /// -  `one_bits`: expected number of one bits after masking the toggle pattern
-fn pattern_by_xor_mask_tests(patterns: &[u8; 10], tests: &[(u8, u32)]) -> u8 {
-    patterns
+fn pattern_by_xor_mask_tests(patterns: &[u8; 10], tests: &[(u8, u32)]) -> anyhow::Result<u8> {
+    let matches = patterns
         .iter()
         .copied()
         .filter(|&n| tests.iter().all(|test| (n ^ test.0).count_ones() == test.1))
-        .exactly_one()
-        .expect("expected exactly one element here")
+        .collect_vec();
+    match matches.as_slice() {
+        [pattern] => Ok(*pattern),
+        _ => bail!(
+            "expected exactly one matching toggle pattern but found {}",
+            matches.len()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    #[test]
+    fn decode_well_formed() {
+        let log: DisplayLog = LINE.parse().unwrap();
+        assert_eq!(log.decode_display_patterns().unwrap(), 5353);
+        assert_eq!(log.count_quickly_decodable_display_patterns(), 0);
+    }
+
+    #[test]
+    fn ambiguous_digit_patterns() {
+        let line = LINE.replace(" dab ", " ag ");
+        let err = line.parse::<DisplayLog>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot uniquely resolve the pattern for digit 1"
+        );
+        assert_eq!(
+            err.root_cause().to_string(),
+            "expected exactly one matching toggle pattern but found 2"
+        );
+    }
+
+    #[test]
+    fn malformed_scribbles() {
+        let err = pattern_from_scribbles("abca").unwrap_err();
+        assert_eq!(err.to_string(), "duplicated character: 'a'");
+        assert!(pattern_from_scribbles("abh").is_err());
+        assert!(LINE.replace(" | ", " ").parse::<DisplayLog>().is_err());
+    }
 }
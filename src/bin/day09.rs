@@ -1,17 +1,15 @@
 //! Day 9: Smoke Basin, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/9>
 use std::cmp::Reverse;
-use std::collections::VecDeque;
 use std::io::{BufRead, BufReader};
 
-use anyhow::Context;
 use clap::Parser;
 use itertools::Itertools;
-use nalgebra::{DMatrix, Dim, Matrix, RawStorage, RowDVector};
+use nalgebra::{DMatrix, Dim, Matrix, RawStorage};
 
 use aoc2021::argparser::Cli;
-use aoc2021::grid::{GridIndices, OrthAdjacent};
-use aoc2021::hashing::HashSet;
+use aoc2021::grid::{flood_fill, GridIndices, OrthAdjacent};
+use aoc2021::parsing_grid::parse_digit_grid;
 
 /// Main program
 fn main() {
@@ -20,25 +18,27 @@ fn main() {
     let Input { heightmap } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Find all low points in the heightmap
-    let low_points: Vec<_> = GridIndices::row_major(heightmap.shape())
-        .filter(|&pos| {
-            OrthAdjacent::new(pos)
-                .within_shape(heightmap.shape())
-                .all(|other_pos| heightmap[pos] < heightmap[other_pos])
-        })
-        .collect();
+    let low_points = low_points(&heightmap, |height, other_height| height < other_height);
 
     // Part 1: Sum or risk levels of the seafloor heightmap
-    let p1_answer: i64 = low_points.iter().map(|&pos| heightmap[pos] + 1).sum();
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer: i64 = cli.time_part("Part 1", || {
+            low_points.iter().map(|&pos| heightmap[pos] + 1).sum()
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Find three largest basins
-    let p2_answer: usize = {
-        let basin_sizes = low_points.iter().map(|&pos| basin_size(pos, &heightmap));
-        let top_basin_sizes = basin_sizes.map(Reverse).k_smallest(3).map(|s| s.0);
-        top_basin_sizes.into_iter().product()
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer: usize = cli.time_part("Part 2", || {
+            let basin_sizes = low_points
+                .iter()
+                .map(|&pos| basin_size(pos, &heightmap, |height| height < 9));
+            let top_basin_sizes = basin_sizes.map(Reverse).k_smallest(3).map(|s| s.0);
+            top_basin_sizes.into_iter().product()
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -51,43 +51,74 @@ struct Input {
 impl Input {
     /// Parses program input from buffered reader.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
-        let mut elements = Vec::new();
-        for line in reader.lines() {
-            let mut row_elements = Vec::new();
-            for c in line?.trim().chars() {
-                let d = c.to_digit(10).with_context(|| {
-                    format!(
-                        "invalid character in decimal string: '{}'",
-                        c.escape_default()
-                    )
-                })? as i64;
-                row_elements.push(d)
-            }
-            elements.push(RowDVector::from_vec(row_elements));
-        }
-        let heightmap = DMatrix::from_rows(elements.as_slice());
+        let heightmap = parse_digit_grid(reader)?;
         Ok(Input { heightmap })
     }
 }
 
-/// Uses breadth-first search to find the basin
-/// whose low point is the same as given in the function parameter.
-fn basin_size<R, C, S>(low_point: (usize, usize), heightmap: &Matrix<i64, R, C, S>) -> usize
+/// Finds all low points in the grid, i.e. grid points which are lower than
+/// all of their orthogonally adjacent grid points according to the predicate `is_lower`
+/// (which checks whether the first height is lower than the second).
+fn low_points<R, C, S, F>(grid: &Matrix<i64, R, C, S>, is_lower: F) -> Vec<(usize, usize)>
 where
     R: Dim,
     C: Dim,
     S: RawStorage<i64, R, C>,
+    F: Fn(i64, i64) -> bool,
 {
-    let shape = heightmap.shape();
-    let mut queue = VecDeque::from([low_point]);
-    let mut visited = HashSet::from_iter([low_point]);
-    while let Some(pos) = queue.pop_front() {
-        for other_pos in OrthAdjacent::new(pos).within_shape(shape) {
-            if heightmap[other_pos] < 9 && !visited.contains(&other_pos) {
-                queue.push_back(other_pos);
-                visited.insert(other_pos);
-            }
-        }
+    GridIndices::row_major(grid.shape())
+        .filter(|&pos| {
+            OrthAdjacent::new(pos)
+                .within_shape(grid.shape())
+                .all(|other_pos| is_lower(grid[pos], grid[other_pos]))
+        })
+        .collect()
+}
+
+/// Finds the size of the basin containing the `start` grid point,
+/// where the predicate `belongs` determines whether a height is part of a basin.
+fn basin_size<R, C, S, B>(start: (usize, usize), grid: &Matrix<i64, R, C, S>, belongs: B) -> usize
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorage<i64, R, C>,
+    B: Fn(i64) -> bool,
+{
+    flood_fill(start, grid.shape(), |pos| belongs(grid[pos])).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day09_sample.txt");
+
+    #[test]
+    fn low_points_sample() {
+        let Input { heightmap } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let points = low_points(&heightmap, |height, other_height| height < other_height);
+        assert_eq!(points, vec![(0, 1), (0, 9), (2, 2), (4, 6)]);
+        let risk: i64 = points.iter().map(|&pos| heightmap[pos] + 1).sum();
+        assert_eq!(risk, 15);
+    }
+
+    #[test]
+    fn basin_size_sample() {
+        let Input { heightmap } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let sizes: Vec<_> = [(0, 1), (0, 9), (2, 2), (4, 6)]
+            .into_iter()
+            .map(|pos| basin_size(pos, &heightmap, |height| height < 9))
+            .collect();
+        assert_eq!(sizes, vec![3, 9, 14, 9]);
+    }
+
+    #[test]
+    fn basin_size_custom_boundary() {
+        let Input { heightmap } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let sizes: Vec<_> = [(0, 1), (0, 9), (2, 2), (4, 6)]
+            .into_iter()
+            .map(|pos| basin_size(pos, &heightmap, |height| height < 7))
+            .collect();
+        assert_eq!(sizes, vec![3, 9, 3, 4]);
     }
-    visited.len()
 }
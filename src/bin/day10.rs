@@ -6,12 +6,20 @@ use clap::Parser;
 use lazy_static::lazy_static;
 
 use aoc2021::argparser::Cli;
+use aoc2021::delimiters::{match_delimiters, DelimiterResult};
+
+/// Table of all pairs of opening and closing characters together with their scores
+const BRACKET_PAIRS: [BracketPair; 4] = [
+    BracketPair::new('(', ')', 3, 1),
+    BracketPair::new('[', ']', 57, 2),
+    BracketPair::new('{', '}', 1197, 3),
+    BracketPair::new('<', '>', 25137, 4),
+];
 
 lazy_static! {
-    /// Mapping from closing character to error score
-    static ref ERROR_SCORE_BY_CHAR: [(char, i64); 4] = [(')', 3), (']', 57), ('}', 1197), ('>', 25137)];
-    /// Mapping from closing character to individual autocompletion score
-    static ref AUTOCOMPLETE_SCORE_BY_CHAR: [(char, i64); 4] = [(')', 1), (']', 2), ('}', 3), ('>', 4)];
+    /// Pairs of opening and closing characters derived from [`BRACKET_PAIRS`]
+    static ref DELIMITER_PAIRS: Vec<(char, char)> =
+        BRACKET_PAIRS.iter().map(|pair| (pair.open, pair.close)).collect();
 }
 
 /// Main program
@@ -21,31 +29,41 @@ fn main() {
     let Input { statements } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Check syntax of all code statements
-    let check_results: Vec<_> = statements.iter().map(check_syntax).collect();
+    let check_results: Vec<_> = statements.iter().map(|s| check_syntax(s)).collect();
 
     // Part 1: Corrupt error score
-    let p1_score: i64 = check_results
-        .iter()
-        .filter_map(|result| match result {
-            SyntaxCheckResult::AutoCompletion(_) => None,
-            SyntaxCheckResult::Corrupted(c) => Some(corrupt_error_score(*c)),
-        })
-        .sum();
-    println!("Part 1 answer: {}", p1_score);
+    if cli.part() != Some(2) {
+        let p1_score: i64 = cli.time_part("Part 1", || {
+            check_results
+                .iter()
+                .filter_map(|result| match result {
+                    DelimiterResult::Incomplete(_) => None,
+                    DelimiterResult::Corrupted(c) => {
+                        Some(corrupt_error_score(*c).expect("unknown character"))
+                    }
+                })
+                .sum()
+        });
+        println!("Part 1 answer: {}", p1_score);
+    }
 
     // Part 2: Autocomplete score
-    let p2_score = {
-        let mut autocomplete_score: Vec<_> = check_results
-            .iter()
-            .filter_map(|result| match result {
-                SyntaxCheckResult::AutoCompletion(s) => Some(autocomplete_score(s)),
-                SyntaxCheckResult::Corrupted(_) => None,
-            })
-            .collect();
-        autocomplete_score.sort_unstable();
-        autocomplete_score[autocomplete_score.len() / 2]
-    };
-    println!("Part 2 answer: {}", p2_score);
+    if cli.part() != Some(1) {
+        let p2_score = cli.time_part("Part 2", || {
+            let mut autocomplete_score: Vec<_> = check_results
+                .iter()
+                .filter_map(|result| match result {
+                    DelimiterResult::Incomplete(s) => {
+                        Some(autocomplete_score(s).expect("unknown character"))
+                    }
+                    DelimiterResult::Corrupted(_) => None,
+                })
+                .collect();
+            autocomplete_score.sort_unstable();
+            autocomplete_score[autocomplete_score.len() / 2]
+        });
+        println!("Part 2 answer: {}", p2_score);
+    }
 }
 
 /// Program input data
@@ -66,60 +84,109 @@ impl Input {
     }
 }
 
-/// Possible outcomes for validating a code statement in submarine navigation subsystem
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum SyntaxCheckResult {
-    /// This struct indicates that, when parsing a statement from left to right,
-    /// no mismatch between designated pairs of characters have been found.
-    /// However, the code may still be incomplete (e.g. hanging open parentheses, brackets, or braces).
-    /// In such case, the string would contain the missing closing characters to complete the code statement.
-    /// If the original code statement is already complete, the autocomplete string would be empty.
-    AutoCompletion(String),
-    /// This struct indicates that, when parsing a statement from left to right,
-    /// a mismatch between designated pairs of characters has been found.
-    /// In such case, it would keep track of the first invalid closing character encountered in the statement.
-    Corrupted(char),
+/// Pair of opening and closing characters along with the scores of the closing character
+#[derive(Debug, Clone, Copy)]
+struct BracketPair {
+    /// Opening character
+    open: char,
+    /// Closing character
+    close: char,
+    /// Corrupt error score when the closing character is mismatched
+    error_score: i64,
+    /// Individual autocompletion score when the closing character is missing
+    autocomplete_score: i64,
 }
 
-/// Checks the syntax of a line of code from submarine subsystem.
-fn check_syntax<T: AsRef<str>>(s: T) -> SyntaxCheckResult {
-    let s = s.as_ref();
-    let mut stack = Vec::with_capacity(16);
-    for c in s.chars() {
-        match (stack.last(), c) {
-            (_, '(' | '[' | '{' | '<') => stack.push(c),
-            (Some(&'('), ')') | (Some(&'['), ']') | (Some(&'{'), '}') | (Some(&'<'), '>') => {
-                stack.pop();
-            }
-            _ => return SyntaxCheckResult::Corrupted(c),
-        };
+impl BracketPair {
+    /// Creates a new bracket pair.
+    const fn new(open: char, close: char, error_score: i64, autocomplete_score: i64) -> Self {
+        BracketPair {
+            open,
+            close,
+            error_score,
+            autocomplete_score,
+        }
+    }
+
+    /// Finds the bracket pair with the given closing character.
+    fn by_close(c: char) -> Option<&'static BracketPair> {
+        BRACKET_PAIRS.iter().find(|pair| pair.close == c)
     }
-    let auto_completion: String = stack
-        .into_iter()
-        .rev()
-        .map(|c| match c {
-            '(' => ')',
-            '[' => ']',
-            '{' => '}',
-            '<' => '>',
-            _ => panic!("this character should never appear in the stack: {}", c),
-        })
-        .collect();
-    SyntaxCheckResult::AutoCompletion(auto_completion)
+}
+
+/// Checks the syntax of the code statement against all pairs in [`BRACKET_PAIRS`].
+/// Characters which are not brackets are ignored.
+fn check_syntax(s: &str) -> DelimiterResult {
+    match_delimiters(s, DELIMITER_PAIRS.as_slice())
 }
 
 /// Computes the corrupt error score for the given closing character.
-fn corrupt_error_score(target: char) -> i64 {
-    let mut it = ERROR_SCORE_BY_CHAR.iter().copied();
-    let find_result = it.find(|&(c, _v)| c == target).expect("unknown character");
-    find_result.1
+/// Returns `None` if the character is not a known closing character.
+fn corrupt_error_score(target: char) -> Option<i64> {
+    BracketPair::by_close(target).map(|pair| pair.error_score)
 }
 
 /// Computes the autocomplete score for the given autocompletion string.
-fn autocomplete_score<T: AsRef<str>>(s: T) -> i64 {
-    s.as_ref().chars().fold(0, |acc, target| {
-        let mut it = AUTOCOMPLETE_SCORE_BY_CHAR.iter().copied();
-        let find_result = it.find(|&(c, _v)| c == target).expect("unknown character");
-        5 * acc + find_result.1
+/// Returns `None` if the string contains an unknown closing character.
+fn autocomplete_score<T: AsRef<str>>(s: T) -> Option<i64> {
+    s.as_ref().chars().try_fold(0, |acc, target| {
+        let pair = BracketPair::by_close(target)?;
+        Some(5 * acc + pair.autocomplete_score)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day10_sample.txt");
+
+    #[test]
+    fn check_syntax_sample() {
+        let Input { statements } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let results: Vec<_> = statements.iter().map(|s| check_syntax(s)).collect();
+        let corrupted: Vec<_> = results
+            .iter()
+            .filter_map(|result| match result {
+                DelimiterResult::Corrupted(c) => Some(*c),
+                DelimiterResult::Incomplete(_) => None,
+            })
+            .collect();
+        assert_eq!(corrupted, vec!['}', ')', ']', ')', '>']);
+        assert_eq!(
+            results[0],
+            DelimiterResult::Incomplete("}}]])})]".to_string())
+        );
+    }
+
+    #[test_case("([]{<>})", DelimiterResult::Incomplete(String::new()); "complete")]
+    #[test_case("[(<", DelimiterResult::Incomplete(">)]".to_string()); "incomplete")]
+    #[test_case("(]", DelimiterResult::Corrupted(']'); "corrupted")]
+    fn check_syntax_by_string(s: &str, expected: DelimiterResult) {
+        assert_eq!(check_syntax(s), expected);
+    }
+
+    #[test_case(')', Some(3); "parenthesis")]
+    #[test_case(']', Some(57); "square bracket")]
+    #[test_case('}', Some(1197); "curly brace")]
+    #[test_case('>', Some(25137); "angle bracket")]
+    #[test_case('(', None; "opening character")]
+    #[test_case('x', None; "unknown character")]
+    fn corrupt_error_score_by_character(target: char, expected: Option<i64>) {
+        assert_eq!(corrupt_error_score(target), expected);
+    }
+
+    #[test_case(")", Some(1); "parenthesis")]
+    #[test_case("]", Some(2); "square bracket")]
+    #[test_case("}", Some(3); "curly brace")]
+    #[test_case(">", Some(4); "angle bracket")]
+    #[test_case("", Some(0); "empty")]
+    #[test_case("}}]])})]", Some(288957); "sample long")]
+    #[test_case("])}>", Some(294); "sample short")]
+    #[test_case("])x", None; "unknown character")]
+    fn autocomplete_score_by_string(s: &str, expected: Option<i64>) {
+        assert_eq!(autocomplete_score(s), expected);
+    }
+}
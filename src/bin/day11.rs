@@ -1,8 +1,7 @@
 //! Day 11: Dumbo Octopus, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/11>
 use std::collections::VecDeque;
-use std::io;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader};
 use std::ops::ControlFlow;
 
 use anyhow::{ensure, Context};
@@ -11,7 +10,7 @@ use nalgebra::{Dim, Matrix, RawStorage, RawStorageMut, SMatrix};
 
 use aoc2021::argparser::Cli;
 use aoc2021::collect_array::CollectArray;
-use aoc2021::grid::{KingAdjacent, MatrixExt};
+use aoc2021::grid::{render_grid, KingAdjacent, MatrixExt};
 use aoc2021::hashing::HashSet;
 
 /// Main program
@@ -21,33 +20,22 @@ fn main() {
     let Input { grid } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Check the input grid
-    let mut debug_writer = io::LineWriter::new(io::stderr());
-    write_grid(&mut debug_writer, &grid).expect("error while printing a grid to stderr");
+    eprintln!("{}", render_grid(&grid));
 
     // Part 1: Number of flashes after 100 steps
-    let p1_answer: usize = {
-        let mut grid = grid; // make a copy
-        (0..100).map(|_| update_grid(&mut grid)).sum()
-    };
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer: usize = cli.time_part("Part 1", || {
+            let mut grid = grid; // make a copy
+            simulate(&mut grid, 100).into_iter().sum()
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Number of steps to get first simultaneous flashes
-    let p2_answer: usize = {
-        let mut grid = grid; // make a copy
-        let result = (1..).try_for_each(|i| {
-            update_grid(&mut grid);
-            if grid_just_all_flashed(&grid) {
-                ControlFlow::Break(i)
-            } else {
-                ControlFlow::Continue(())
-            }
-        });
-        match result {
-            ControlFlow::Continue(_) => unreachable!(),
-            ControlFlow::Break(attempts) => attempts,
-        }
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || first_all_flash_step(grid));
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -72,11 +60,46 @@ impl Input {
             }
             elements.push(row_elements.into_iter().collect_exact()?);
         }
-        let grid = SMatrix::from(elements.into_iter().collect_exact()?);
+        // Arrays are taken as columns by `SMatrix::from`, hence the transpose
+        let grid: SMatrix<u8, 10, 10> = SMatrix::from(elements.into_iter().collect_exact()?);
+        let grid = grid.transpose();
         Ok(Input { grid })
     }
 }
 
+/// Runs the octopus simulation on the grid in-place for the given number of `steps`,
+/// and returns the number of flashed octopuses at each step.
+fn simulate<R, C, S>(grid: &mut Matrix<u8, R, C, S>, steps: usize) -> Vec<usize>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorageMut<u8, R, C>,
+{
+    (0..steps).map(|_| update_grid(grid)).collect()
+}
+
+/// Runs the octopus simulation on a copy of the grid until all octopuses flash simultaneously,
+/// and returns the (one-based) step number at which this first happens.
+fn first_all_flash_step<R, C, S>(mut grid: Matrix<u8, R, C, S>) -> usize
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorageMut<u8, R, C>,
+{
+    let result = (1..).try_for_each(|i| {
+        update_grid(&mut grid);
+        if grid_just_all_flashed(&grid) {
+            ControlFlow::Break(i)
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    match result {
+        ControlFlow::Continue(_) => unreachable!(),
+        ControlFlow::Break(attempts) => attempts,
+    }
+}
+
 /// Updates the state of octopus grid in-place, and returns the number of flashed octopuses.
 /// - TODO: Function could have been more generic on Matrix type
 fn update_grid<R, C, S>(grid: &mut Matrix<u8, R, C, S>) -> usize
@@ -129,23 +152,44 @@ where
     grid.indices().all(|pos| grid[pos] == 0)
 }
 
-/// Printing the grid as the debugging method.
-/// - TODO: Learn proper logging best practices
-fn write_grid<R, C, S>(writer: &mut impl Write, grid: &Matrix<u8, R, C, S>) -> anyhow::Result<()>
-where
-    R: Dim,
-    C: Dim,
-    S: RawStorage<u8, R, C>,
-{
-    let (nrows, ncols) = grid.shape();
-    for i in 0..nrows {
-        let mut buffer: String = (0..ncols)
-            .map(|j| char::from_digit(grid[(i, j)] as u32, 10).unwrap())
-            .collect();
-        buffer.push('\n');
-        writer
-            .write_all(buffer.as_bytes())
-            .expect("error while writing grid info");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day11_sample.txt");
+
+    #[test]
+    fn simulate_sample() {
+        let Input { mut grid } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(render_grid(&grid), SAMPLE.trim_end());
+        let flashes = simulate(&mut grid, 2);
+        assert_eq!(flashes, vec![0, 35]);
+        let expected = [
+            "8807476555",
+            "5089087054",
+            "8597889608",
+            "8485769600",
+            "8700908800",
+            "6600088989",
+            "6800005943",
+            "0000007456",
+            "9000000876",
+            "8700006848",
+        ];
+        assert_eq!(render_grid(&grid), expected.join("\n"));
+        let flashes = simulate(&mut grid, 8);
+        assert_eq!(35 + flashes.iter().sum::<usize>(), 204);
+    }
+
+    #[test]
+    fn simulate_hundred_steps() {
+        let Input { mut grid } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(simulate(&mut grid, 100).into_iter().sum::<usize>(), 1656);
+    }
+
+    #[test]
+    fn first_all_flash_step_sample() {
+        let Input { grid } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(first_all_flash_step(grid), 195);
     }
-    Ok(())
 }
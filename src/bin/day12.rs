@@ -2,7 +2,7 @@
 //! <https://adventofcode.com/2021/day/12>
 use std::io::{BufRead, BufReader};
 
-use anyhow::Context;
+use anyhow::{ensure, Context};
 use clap::Parser;
 use itertools::Itertools;
 
@@ -17,54 +17,22 @@ fn main() {
     let Input { graph } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Visiting each small cave at most once
-    let p1_answer = {
-        let mut count: usize = 0;
-        graph.exhaustive_traverse(
-            "start",
-            "end",
-            // Acceptable cases:
-            // 1.  The next node is a big cave (containing uppercase letters), or
-            // 2.  The path so far does _not_ contain such next node
-            |next, path| next.chars().any(char::is_uppercase) || !path.contains(&next),
-            |_path| {
-                // eprintln!("=> {}", _path.join(", "));
-                count += 1
-            },
-        );
-        count
-    };
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            graph.count_paths("start", "end", VisitPolicy::SmallOnce)
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Visiting each small cave at most once,
     // except for one that is allowed up to twice
     // but excluding the start and the end
-    let p2_answer = {
-        let mut count = 0;
-        graph.exhaustive_traverse(
-            "start",
-            "end",
-            // Acceptable cases (the first two are the same as part 1):
-            // 1.  The next node is a big cave (containing uppercase letters), or
-            // 2.  The path so far does _not_ contain such next node, or
-            // 3.  The next node does _not_ go back to "start"
-            //     AND all previous small caves are unique visits (new!)
-            |next, path| {
-                next.chars().all(char::is_uppercase)
-                    || !path.contains(&next)
-                    || next.ne("start")
-                        && path
-                            .iter()
-                            .filter(|prev| !prev.chars().all(char::is_uppercase))
-                            .all_unique()
-            },
-            |_path| {
-                // eprintln!("=> {}", _path.join(", "));
-                count += 1
-            },
-        );
-        count
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            graph.count_paths("start", "end", VisitPolicy::OneSmallTwice)
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -76,6 +44,8 @@ struct Input {
 
 impl Input {
     /// Parses program input from buffered reader.
+    /// Graphs with two adjacent big caves are rejected
+    /// since both parts of the puzzle would have infinitely many paths.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
         let mut graph = Graph::new();
         for line in reader.lines() {
@@ -84,10 +54,57 @@ impl Input {
             graph.add_edge(u, v);
             graph.add_edge(v, u);
         }
+        ensure!(
+            !graph.has_adjacent_big_caves(),
+            "big caves must not be adjacent to each other"
+        );
         Ok(Input { graph })
     }
 }
 
+/// Rules determining which caves may be visited next given the path walked so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitPolicy {
+    /// Small caves are visited at most once whereas big caves are visited any number of times.
+    /// Note that the traversal never ends if two big caves are adjacent to each other.
+    SmallOnce,
+    /// Same as [`VisitPolicy::SmallOnce`] except that a single small cave
+    /// (other than the start) may be visited twice.
+    OneSmallTwice,
+    /// Small caves are visited at most once and each big cave is visited at most the given times,
+    /// which bounds the traversal even when big caves form cycles.
+    /// Puzzle inputs never need it since [`Input`] rejects adjacent big caves.
+    #[cfg(test)]
+    BigLimited(usize),
+}
+
+/// Checks whether the cave is big, i.e. its identifier is written in uppercase.
+fn is_big(cave: &str) -> bool {
+    cave.chars().all(char::is_uppercase)
+}
+
+impl VisitPolicy {
+    /// Decides whether the `next` cave may be visited given the `path` walked so far.
+    fn allows(&self, next: &str, path: &[&str]) -> bool {
+        match self {
+            VisitPolicy::SmallOnce => is_big(next) || !path.contains(&next),
+            VisitPolicy::OneSmallTwice => {
+                is_big(next)
+                    || !path.contains(&next)
+                    || next.ne("start") && path.iter().filter(|prev| !is_big(prev)).all_unique()
+            }
+            #[cfg(test)]
+            VisitPolicy::BigLimited(limit) => {
+                if is_big(next) {
+                    path.iter().filter(|&&prev| prev == next).count() < *limit
+                } else {
+                    !path.contains(&next)
+                }
+            }
+        }
+    }
+}
+
 /// Graph data with adjacency list data structure.
 #[derive(Debug, Clone)]
 struct Graph {
@@ -103,6 +120,13 @@ impl Graph {
         }
     }
 
+    /// Checks whether any two big caves are connected directly to each other.
+    fn has_adjacent_big_caves(&self) -> bool {
+        self.adjlists
+            .iter()
+            .any(|(u, vs)| is_big(u) && vs.iter().any(|v| is_big(v)))
+    }
+
     /// Add a directed edge from node `u` to node `v`.
     ///
     /// # Implementation Note
@@ -115,24 +139,36 @@ impl Graph {
     {
         let u = u.as_ref().to_string();
         let v = v.as_ref().to_string();
-        self.adjlists.entry(u).or_insert_with(Vec::new).push(v);
+        self.adjlists.entry(u).or_default().push(v);
+    }
+
+    /// Counts the number of distinct paths from `start` to `end` permitted by the visit `policy`.
+    fn count_paths<T>(&self, start: T, end: T, policy: VisitPolicy) -> usize
+    where
+        T: AsRef<str>,
+    {
+        let mut count = 0;
+        self.exhaustive_traverse(start, end, policy, |_path| {
+            // eprintln!("=> {}", _path.join(", "));
+            count += 1
+        });
+        count
     }
 
     /// Exhaustive path searching from `start` to `end`.
     /// Before the function decides to queue up walking onto an adjacent node,
-    /// the predicate `decide_should_walk` decides whether to proceed
+    /// the visit `policy` decides whether to proceed
     /// based on the identifier of such node, and the path walked so far from the `start`.
     /// Once and each time a finished path from `start` to `end` has been found,
     /// the function `process_finished_path` is invoked with such path for further processing.
-    fn exhaustive_traverse<T, P, F>(
+    fn exhaustive_traverse<T, F>(
         &self,
         start: T,
         end: T,
-        mut decide_should_walk: P,
+        policy: VisitPolicy,
         mut process_finished_path: F,
     ) where
         T: AsRef<str>,
-        P: FnMut(&str, &[&str]) -> bool,
         F: FnMut(&[&str]),
     {
         #[derive(Debug, Eq, PartialEq)]
@@ -154,7 +190,7 @@ impl Graph {
                         process_finished_path(depth_stack.as_slice());
                     } else {
                         for next in self.adjlists[curr].iter() {
-                            if decide_should_walk(next, depth_stack.as_slice()) {
+                            if policy.allows(next, depth_stack.as_slice()) {
                                 event_stack.push(Event::PreStack(next))
                             }
                         }
@@ -167,3 +203,66 @@ impl Graph {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn sample_graph(input: &str) -> Graph {
+        Input::from_buffer(input.as_bytes()).unwrap().graph
+    }
+
+    const SMALL_SAMPLE: &str = include_str!("../../inputs/day12_small_smaple.txt");
+    const MEDIUM_SAMPLE: &str = include_str!("../../inputs/day12_medium_smaple.txt");
+    const LARGE_SAMPLE: &str = include_str!("../../inputs/day12_large_smaple.txt");
+
+    #[test_case(SMALL_SAMPLE, 10; "small")]
+    #[test_case(MEDIUM_SAMPLE, 19; "medium")]
+    #[test_case(LARGE_SAMPLE, 226; "large")]
+    fn part1_samples(input: &str, expected: usize) {
+        let graph = sample_graph(input);
+        assert!(!graph.has_adjacent_big_caves());
+        assert_eq!(
+            graph.count_paths("start", "end", VisitPolicy::SmallOnce),
+            expected
+        );
+    }
+
+    #[test_case(SMALL_SAMPLE, 36; "small")]
+    #[test_case(MEDIUM_SAMPLE, 103; "medium")]
+    #[test_case(LARGE_SAMPLE, 3509; "large")]
+    fn part2_samples(input: &str, expected: usize) {
+        let graph = sample_graph(input);
+        assert_eq!(
+            graph.count_paths("start", "end", VisitPolicy::OneSmallTwice),
+            expected
+        );
+    }
+
+    #[test]
+    fn reject_adjacent_big_caves() {
+        assert!(Input::from_buffer("start-A\nA-B\nB-end\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn bounded_adjacent_big_caves() {
+        let mut graph = Graph::new();
+        for (u, v) in [("start", "A"), ("A", "B"), ("B", "end"), ("A", "c")] {
+            graph.add_edge(u, v);
+            graph.add_edge(v, u);
+        }
+        assert!(graph.has_adjacent_big_caves());
+        // Only start,A,B,end
+        assert_eq!(
+            graph.count_paths("start", "end", VisitPolicy::BigLimited(1)),
+            1
+        );
+        // Additionally start,A,B,A,B,end and start,A,c,A,B,end
+        assert_eq!(
+            graph.count_paths("start", "end", VisitPolicy::BigLimited(2)),
+            3
+        );
+    }
+}
@@ -10,8 +10,10 @@ use regex::Regex;
 
 use aoc2021::argparser::Cli;
 use aoc2021::collect_array::CollectArray;
+use aoc2021::grid::DynGrid;
 use aoc2021::hashing::HashSet;
 use aoc2021::parsing::QuickParse;
+use aoc2021::reflect::reflect;
 
 /// Main program
 fn main() {
@@ -20,24 +22,24 @@ fn main() {
     let Input { dots, fold_instrs } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: First fold only
-    let p1_dot_count = {
-        let dots: HashSet<Point> = dots
-            .iter()
-            .map(|dot| fold_instrs[0].fold_point(*dot))
-            .collect();
-        dots.len()
-    };
-    println!("Part 1 answer: {}", p1_dot_count);
+    if cli.part() != Some(2) {
+        let p1_dot_count = cli.time_part("Part 1", || {
+            let dots: HashSet<Point> = dots
+                .iter()
+                .map(|dot| fold_instrs[0].fold_point(*dot))
+                .collect();
+            dots.len()
+        });
+        println!("Part 1 answer: {}", p1_dot_count);
+    }
 
     // Part 2: Fold and print result
-    let dots: HashSet<Point> = fold_instrs
-        .iter()
-        .fold(dots.into_iter().collect(), |dots, instr| {
-            dots.into_iter().map(|dot| instr.fold_point(dot)).collect()
-        });
-    let mut debug_writer = io::LineWriter::new(io::stdout());
-    println!("Part 2 answer: (see below)");
-    write_dots(&mut debug_writer, &dots).expect("error while printing dots to stderr");
+    if cli.part() != Some(1) {
+        let grid = cli.time_part("Part 2", || fold_all(&dots, &fold_instrs));
+        let mut debug_writer = io::LineWriter::new(io::stdout());
+        println!("Part 2 answer: (see below)");
+        write_grid(&mut debug_writer, &grid).expect("error while printing dots to stderr");
+    }
 }
 
 /// Program input data
@@ -108,31 +110,42 @@ enum FoldInstr {
 impl FoldInstr {
     /// Folds a given dot over the instruction to produce a new dot.
     fn fold_point(&self, dot: Point) -> Point {
-        fn fold_around(value: u64, around: u64) -> u64 {
-            u64::min(value, 2 * around - value)
-        }
         match self {
             FoldInstr::XEquals(c) => Point {
-                x: fold_around(dot.x, *c),
+                x: reflect(dot.x, *c),
                 y: dot.y,
             },
             FoldInstr::YEquals(c) => Point {
                 x: dot.x,
-                y: fold_around(dot.y, *c),
+                y: reflect(dot.y, *c),
             },
         }
     }
 }
 
-/// Prints the dots as the debugging mechanisms
-fn write_dots(writer: &mut impl Write, dots: &HashSet<Point>) -> anyhow::Result<()> {
-    ensure!(!dots.is_empty(), "empty dots specified");
-    let nrows = *dots.iter().map(|Point { x: _, y }| y).max().unwrap() + 1;
-    let ncols = *dots.iter().map(|Point { x, y: _ }| x).max().unwrap() + 1;
+/// Applies all folding instructions to the dots and rasterizes the surviving dots
+/// into a boolean grid indexed by `(y, x)` whose shape fits the largest coordinates.
+fn fold_all(dots: &[Point], instrs: &[FoldInstr]) -> DynGrid<bool> {
+    let dots: HashSet<Point> = dots
+        .iter()
+        .map(|&dot| instrs.iter().fold(dot, |dot, instr| instr.fold_point(dot)))
+        .collect();
+    let nrows = dots.iter().map(|dot| dot.y + 1).max().unwrap_or(0);
+    let ncols = dots.iter().map(|dot| dot.x + 1).max().unwrap_or(0);
+    let rows = (0..nrows)
+        .map(|y| (0..ncols).map(|x| dots.contains(&Point { x, y })).collect())
+        .collect();
+    DynGrid::from_rows(rows).unwrap()
+}
+
+/// Prints the grid of dots as the debugging mechanisms
+fn write_grid(writer: &mut impl Write, grid: &DynGrid<bool>) -> anyhow::Result<()> {
+    let (nrows, ncols) = grid.shape();
+    ensure!(nrows > 0 && ncols > 0, "empty dots specified");
     for y in 0..nrows {
         let mut buffer: String = (0..ncols)
             .map(|x| {
-                if dots.contains(&Point { x, y }) {
+                if grid[(y, x)] {
                     "🟨"
                 } else {
                     "⬛️" // this emoji contains two codepoints
@@ -146,3 +159,28 @@ fn write_dots(writer: &mut impl Write, dots: &HashSet<Point>) -> anyhow::Result<
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day13_sample.txt");
+
+    #[test]
+    fn fold_all_sample() {
+        let Input { dots, fold_instrs } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let grid = fold_all(&dots, &fold_instrs);
+        let expected = ["#####", "#...#", "#...#", "#...#", "#####"];
+        assert_eq!(
+            grid.to_char_string(|&dot| if dot { '#' } else { '.' }),
+            expected.join("\n")
+        );
+    }
+
+    #[test]
+    fn fold_all_without_dots() {
+        let grid = fold_all(&[], &[FoldInstr::YEquals(7)]);
+        assert_eq!(grid.shape(), (0, 0));
+        assert!(write_grid(&mut Vec::new(), &grid).is_err());
+    }
+}
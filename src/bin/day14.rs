@@ -1,7 +1,7 @@
 //! Day 14: Extended Polymerization, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/14>
-use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
+use std::iter::once;
 use std::str::FromStr;
 
 use anyhow::{bail, Context};
@@ -12,6 +12,7 @@ use regex::Regex;
 
 use aoc2021::argparser::Cli;
 use aoc2021::collect_array::CollectArray;
+use aoc2021::hashing::{Counter, CountsFast, HashMap};
 use aoc2021::parsing::QuickParse;
 
 /// Main program
@@ -23,34 +24,21 @@ fn main() {
         ins_rules,
     } = Input::from_buffer(input_reader).expect("cannot parse input");
 
-    // Save first and last chars for reconciliation later
-    let first = template.chars().next().expect("empty template string");
-    let last = template.chars().last().unwrap();
-
-    // Count bigrams or the original template polymer
-    let bigram_counts = template.chars().tuple_windows::<(_, _)>().counts();
-
     // Part 1: Applying insertion rules 10 times
-    let bigram_counts = (0..10).fold(bigram_counts, |counts, _| {
-        next_polymer_bigram_counts(&counts, ins_rules.as_slice())
-    });
-    let p1_diff = {
-        let unigram_counts = unigrams_from_bigrams(first, last, &bigram_counts);
-        let (&min_count, &max_count) = unigram_counts.values().minmax().into_option().unwrap();
-        max_count - min_count
-    };
-    println!("Part 1 answer: {}", p1_diff);
+    if cli.part() != Some(2) {
+        let p1_diff = cli.time_part("Part 1", || {
+            grow_and_score(template.as_str(), ins_rules.as_slice(), 10)
+        });
+        println!("Part 1 answer: {}", p1_diff);
+    }
 
-    // Part 2: Apply insertion rules 30 more times
-    let bigram_counts = (0..30).fold(bigram_counts, |counts, _| {
-        next_polymer_bigram_counts(&counts, ins_rules.as_slice())
-    });
-    let p2_diff = {
-        let unigram_counts = unigrams_from_bigrams(first, last, &bigram_counts);
-        let (&min_count, &max_count) = unigram_counts.values().minmax().into_option().unwrap();
-        max_count - min_count
-    };
-    println!("Part 2 answer: {}", p2_diff);
+    // Part 2: Apply insertion rules 40 times
+    if cli.part() != Some(1) {
+        let p2_diff = cli.time_part("Part 2", || {
+            grow_and_score(template.as_str(), ins_rules.as_slice(), 40)
+        });
+        println!("Part 2 answer: {}", p2_diff);
+    }
 }
 
 /// Program input data
@@ -94,8 +82,8 @@ type Bigram = (char, char);
 struct InsertionRule {
     /// Pair of characters to capture
     pattern: Bigram,
-    /// Character to insert between the pair of pattern characters
-    insert_char: char,
+    /// Non-empty string of characters to insert between the pair of pattern characters
+    insertion: String,
 }
 
 impl FromStr for InsertionRule {
@@ -103,56 +91,116 @@ impl FromStr for InsertionRule {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"\s*(\w\w)\s+->\s+(\w)\s*").unwrap();
+            static ref RE: Regex = Regex::new(r"\s*(\w\w)\s+->\s+(\w+)\s*").unwrap();
         }
-        // let RE: Regex = Regex::new(r"\s*(\w\w)\s+->\s+(\w)\s*").unwrap();
+        // let RE: Regex = Regex::new(r"\s*(\w\w)\s+->\s+(\w+)\s*").unwrap();
         let captures = RE
             .captures(s)
             .with_context(|| format!("invalid insertion rule: {}", s))?;
         let [fst, snd] = captures[1].chars().collect_exact()?;
-        let [insert_char] = captures[2].chars().collect_exact()?;
+        let insertion = captures[2].to_string();
         let pattern = (fst, snd);
-        Ok(InsertionRule {
-            pattern,
-            insert_char,
-        })
+        Ok(InsertionRule { pattern, insertion })
     }
 }
 
+/// Applies the insertion rules to the `template` polymer for the given number of `steps`
+/// and computes the difference between the counts of the most and the least common elements.
+/// An empty template has a difference of zero.
+fn grow_and_score(template: &str, insertion_rules: &[InsertionRule], steps: usize) -> usize {
+    let last = match template.chars().last() {
+        Some(last) => last,
+        None => return 0,
+    };
+    let bigram_counts = template.chars().tuple_windows::<(_, _)>().counts_fast();
+    let bigram_counts = (0..steps).fold(bigram_counts, |counts, _| {
+        next_polymer_bigram_counts(&counts, insertion_rules)
+    });
+    let unigram_counts = unigrams_from_bigrams(last, &bigram_counts);
+    let (min_count, max_count) = unigram_counts.min_max_counts().unwrap();
+    max_count - min_count
+}
+
 /// Computes the bigram counts of the next polymer obtained by transforming the input polymer
 /// (whose bigram counts is given as input) based on `insertion_rules`.
+/// Each matched bigram is replaced by all overlapping bigrams of the pattern
+/// with the inserted string sandwiched in between.
 fn next_polymer_bigram_counts(
     bigram_counts: &HashMap<Bigram, usize>,
     insertion_rules: &[InsertionRule],
 ) -> HashMap<Bigram, usize> {
-    let mut next_bigram_counts = HashMap::new();
+    let mut next_bigram_counts = HashMap::default();
     for rule in insertion_rules {
         let count = bigram_counts.get(&rule.pattern).copied().unwrap_or(0);
         if count == 0 {
             continue;
         }
-        *next_bigram_counts
-            .entry((rule.pattern.0, rule.insert_char))
-            .or_insert(0) += count;
-        *next_bigram_counts
-            .entry((rule.insert_char, rule.pattern.1))
-            .or_insert(0) += count;
+        let chars = once(rule.pattern.0)
+            .chain(rule.insertion.chars())
+            .chain(once(rule.pattern.1));
+        for bigram in chars.tuple_windows() {
+            *next_bigram_counts.entry(bigram).or_insert(0) += count;
+        }
     }
     next_bigram_counts
 }
 
 /// Counts individual elements based on bigram counts of a polymer.
-fn unigrams_from_bigrams(
-    first: char,
-    last: char,
-    bigram_counts: &HashMap<Bigram, usize>,
-) -> HashMap<char, usize> {
-    let mut unigram_counts = HashMap::from([(first, 1), (last, 1)]);
-    for (bigram, count) in bigram_counts.iter() {
-        *unigram_counts.entry(bigram.0).or_insert(0) += count;
-        *unigram_counts.entry(bigram.1).or_insert(0) += count;
+/// Every element except the `last` one is the first element of exactly one bigram.
+fn unigrams_from_bigrams(last: char, bigram_counts: &HashMap<Bigram, usize>) -> Counter<char> {
+    let mut unigram_counts = Counter::new();
+    unigram_counts.add(last);
+    for (bigram, &count) in bigram_counts.iter() {
+        unigram_counts.add_n(bigram.0, count);
     }
-    // Undo double counting
-    unigram_counts.values_mut().for_each(|v| *v /= 2);
     unigram_counts
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day14_sample.txt");
+
+    #[test_case(10, 1588; "10 steps")]
+    #[test_case(40, 2188189693529; "40 steps")]
+    fn grow_and_score_sample(steps: usize, expected: usize) {
+        let input = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            grow_and_score(&input.template, &input.ins_rules, steps),
+            expected
+        );
+    }
+
+    #[test]
+    fn multi_char_insertion() {
+        let rules: Vec<InsertionRule> =
+            vec!["AB -> XY".parse().unwrap(), "BA -> Z".parse().unwrap()];
+        let bigram_counts = "ABA".chars().tuple_windows().counts_fast();
+        let bigram_counts = next_polymer_bigram_counts(&bigram_counts, &rules);
+        let expected: HashMap<Bigram, usize> = [
+            (('A', 'X'), 1),
+            (('X', 'Y'), 1),
+            (('Y', 'B'), 1),
+            (('B', 'Z'), 1),
+            (('Z', 'A'), 1),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(bigram_counts, expected);
+        let unigram_counts = unigrams_from_bigrams('A', &bigram_counts);
+        assert_eq!(unigram_counts.get(&'A'), 2);
+        for c in ['X', 'Y', 'B', 'Z'] {
+            assert_eq!(unigram_counts.get(&c), 1);
+        }
+        assert_eq!(unigram_counts.len(), 5);
+    }
+
+    #[test]
+    fn grow_and_score_empty() {
+        let input = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(grow_and_score("", &input.ins_rules, 10), 0);
+    }
+}
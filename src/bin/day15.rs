@@ -9,7 +9,7 @@ use clap::Parser;
 use nalgebra::{DMatrix, RowDVector};
 
 use aoc2021::argparser::Cli;
-use aoc2021::grid::{GridPoint, OrthAdjacent};
+use aoc2021::grid::{GridPoint, LazyGrid, OrthAdjacent};
 use aoc2021::hashing::HashMap;
 
 /// Main program
@@ -19,32 +19,30 @@ fn main() {
     let Input { risk_levels } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: For input grid
-    let p1_answer = {
-        let (nrows, ncols) = risk_levels.shape();
-        let grid_proxy = GridProxy {
-            shape: (nrows, ncols),
-            proxy_map: |pos: GridPoint<usize>| -> i64 { risk_levels[pos] },
-        };
-        shortest_path(&grid_proxy, (0, 0), (nrows - 1, ncols - 1))
-    };
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            let (nrows, ncols) = risk_levels.shape();
+            let grid = LazyGrid::new((nrows, ncols), |pos| risk_levels[pos]);
+            shortest_path(&grid, (0, 0), (nrows - 1, ncols - 1))
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: For 5×5 extended input grid
-    let p2_answer = {
-        let (nrows, ncols) = risk_levels.shape();
-        let grid_proxy = GridProxy {
-            shape: (5 * nrows, 5 * ncols),
-            proxy_map: Box::new(|(i, j): GridPoint<usize>| -> i64 {
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            let (nrows, ncols) = risk_levels.shape();
+            let grid = LazyGrid::new((5 * nrows, 5 * ncols), |(i, j)| {
                 let item = risk_levels[(i % nrows, j % ncols)] + (i / nrows + j / ncols) as i64;
                 match item % 9 {
                     0 => 9,
                     d => d,
                 }
-            }),
-        };
-        shortest_path(&grid_proxy, (0, 0), (5 * nrows - 1, 5 * ncols - 1))
-    };
-    println!("Part 2 answer: {}", p2_answer);
+            });
+            shortest_path(&grid, (0, 0), (5 * nrows - 1, 5 * ncols - 1))
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -78,7 +76,7 @@ impl Input {
 
 /// Computes the length of the shortest path from `start` to `end` within the grid.
 /// Such length consists of the weight sum of all nodes in the part except the start.
-fn shortest_path<F>(grid: &GridProxy<i64, F>, start: GridPoint<usize>, end: GridPoint<usize>) -> i64
+fn shortest_path<F>(grid: &LazyGrid<i64, F>, start: GridPoint<usize>, end: GridPoint<usize>) -> i64
 where
     F: Fn(GridPoint<usize>) -> i64,
 {
@@ -94,9 +92,9 @@ where
         if cost > dists.get(&pos).copied().unwrap_or(i64::MAX) {
             continue;
         }
-        for other_pos in OrthAdjacent::new(pos).within_shape(grid.shape) {
+        for other_pos in OrthAdjacent::new(pos).within_shape(grid.shape()) {
             let next = State {
-                cost: cost + (grid.proxy_map)(other_pos),
+                cost: cost + grid.get(other_pos),
                 pos: other_pos,
             };
             if next.cost < dists.get(&next.pos).copied().unwrap_or(i64::MAX) {
@@ -129,12 +127,3 @@ impl Ord for State {
             .then_with(|| self.pos.cmp(&other.pos))
     }
 }
-
-/// Proxy for grid type with item looking being computed on-the-fly
-struct GridProxy<T, F>
-where
-    F: Fn(GridPoint<usize>) -> T,
-{
-    shape: GridPoint<usize>,
-    proxy_map: F,
-}
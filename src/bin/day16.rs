@@ -1,55 +1,94 @@
 //! Day 16: Packet Decoder, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/16>
 use std::collections::VecDeque;
-use std::io::Read;
+use std::io::{BufRead, BufReader};
 
 use anyhow::{bail, ensure, Context};
 use clap::Parser;
 use itertools::Itertools;
-use num::PrimInt;
 
 use aoc2021::argparser::Cli;
+use aoc2021::bits::{bits_to_int, hex_to_bits};
 use aoc2021::collect_array::CollectArray;
 
 /// Main program
 fn main() {
     let cli = Cli::parse();
-    let input_reader = cli.input_reader().expect("cannot open file");
-    let mut input_stream = InputStream::new(input_reader);
+    let mut input_reader = BufReader::new(cli.input_reader().expect("cannot open file"));
+    let mut transmission = String::new();
+    input_reader
+        .read_line(&mut transmission)
+        .expect("cannot read transmission");
+    let transmission = transmission.trim();
+    let mut input_stream =
+        InputStream::new(transmission.as_bytes()).with_max_bits(4 * transmission.len());
 
     // Parses the packet from the input stream
-    let packet = Packet::from_stream(&mut input_stream).expect("cannot parse packet");
+    let (packet, _) = Packet::from_stream_sized(&mut input_stream).expect("cannot parse packet");
 
     // Part 1: Sum of version values of all packets
-    let p1_answer = packet
-        .reduce(&|subpacket, children| subpacket.version as u64 + children.iter().sum::<u64>());
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            packet.reduce(&|subpacket, children: &[u64]| {
+                subpacket.version as u64 + children.iter().sum::<u64>()
+            })
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Evaluate the packet
-    let p2_answer = packet.eval().expect("error during evaluation");
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || packet.eval().expect("error during evaluation"));
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Alias for bit type (can either be 0 or 1)
 type Bit = u8;
 
 /// Wrapper over program input to provide the stream as an iterator
-struct InputStream<R: Read> {
+struct InputStream<R: BufRead> {
     source: std::io::Bytes<R>,
     buffer: VecDeque<Bit>,
     bits_read: usize,
+    max_bits: Option<usize>,
 }
 
-impl<R: Read> InputStream<R> {
-    /// Creates a new input stream from [`std::io::Read`] object
+impl<R: BufRead> InputStream<R> {
+    /// Creates a new input stream from [`std::io::BufRead`] object
     fn new(reader: R) -> Self {
         InputStream {
             source: reader.bytes(),
             buffer: VecDeque::with_capacity(4),
             bits_read: 0,
+            max_bits: None,
+        }
+    }
+
+    /// Limits the stream to produce at most `max_bits` bits,
+    /// beyond which reading further bits from the stream will result in an error.
+    fn with_max_bits(self, max_bits: usize) -> Self {
+        InputStream {
+            max_bits: Some(max_bits),
+            ..self
         }
     }
 
+    /// Number of bits read from the stream so far.
+    /// This is not named `position` because [`Iterator::position`] would take precedence
+    /// when called on `&mut InputStream`, which is itself an iterator.
+    fn bit_position(&self) -> usize {
+        self.bits_read
+    }
+
+    /// Ensures that the stream position `position` does not exceed the maximum bit length (if any).
+    fn ensure_within(&self, position: usize) -> anyhow::Result<()> {
+        if let Some(max_bits) = self.max_bits {
+            ensure!(position <= max_bits, "stream exceeded {} bits", max_bits);
+        }
+        Ok(())
+    }
+
     /// Fetches the next few bits from the stream and returns as an array.
     fn fetch<const SIZE: usize>(&mut self) -> anyhow::Result<[Bit; SIZE]> {
         let mut target = [0; SIZE];
@@ -60,16 +99,19 @@ impl<R: Read> InputStream<R> {
     }
 }
 
-impl<R: Read> Iterator for InputStream<R> {
+impl<R: BufRead> Iterator for InputStream<R> {
     type Item = anyhow::Result<Bit>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Err(err) = self.ensure_within(self.bits_read + 1) {
+            return Some(Err(err));
+        }
         if self.buffer.is_empty() {
             let c = match self.source.next()? {
                 Ok(c) => c as char,
                 Err(err) => return Some(Err(anyhow::Error::new(err))),
             };
-            let bits = match bits_from_hex(c) {
+            let bits = match hex_to_bits(c) {
                 Ok(bits) => bits,
                 Err(err) => return Some(Err(err)),
             };
@@ -88,12 +130,14 @@ struct Packet {
 }
 
 impl Packet {
-    /// Parses the packet by consuming from the [`InputStream`].
-    /// If successful, this method returns the number of bits read from the stream
-    /// as well as the packet object itself.
-    fn from_stream<R: Read>(stream: &mut InputStream<R>) -> anyhow::Result<Self> {
-        let version = decimal_from_bits(stream.fetch::<3>()?.as_slice());
-        let type_id = decimal_from_bits(stream.fetch::<3>()?.as_slice());
+    /// Parses the packet by consuming from the [`InputStream`],
+    /// recursing once per nesting level of operator packets.
+    /// The number of bits read can be obtained from [`Packet::from_stream_sized`] instead.
+    /// This serves as the reference parser for [`Packet::from_stream_iterative`] in tests.
+    #[cfg(test)]
+    fn from_stream<R: BufRead>(stream: &mut InputStream<R>) -> anyhow::Result<Self> {
+        let version = bits_to_int(stream.fetch::<3>()?.as_slice());
+        let type_id = bits_to_int(stream.fetch::<3>()?.as_slice());
         let payload = match type_id {
             4 => Payload::parse_literal(stream)?,
             _ => Payload::parse_ops(stream, Operator::new(type_id)?)?,
@@ -101,22 +145,93 @@ impl Packet {
         Ok(Packet { version, payload })
     }
 
-    /// Evaluates the expression described by the packet.
+    /// Parses the packet by consuming from the [`InputStream`].
+    /// Instead of recursing once per nesting level, this method keeps an explicit stack
+    /// of partially-parsed operator packets so that deeply nested packets
+    /// can be parsed within bounded call stack space.
+    fn from_stream_iterative<R: BufRead>(stream: &mut InputStream<R>) -> anyhow::Result<Self> {
+        let mut stack: Vec<OperatorFrame> = Vec::new();
+        loop {
+            // Parse the header of the next packet: literal packets are complete right away
+            // whereas operator packets must wait for all of their subpackets to be parsed
+            let version = bits_to_int(stream.fetch::<3>()?.as_slice());
+            let type_id = bits_to_int(stream.fetch::<3>()?.as_slice());
+            let mut completed = match type_id {
+                4 => Some(Packet {
+                    version,
+                    payload: Payload::parse_literal(stream)?,
+                }),
+                _ => {
+                    stack.push(OperatorFrame::parse(
+                        stream,
+                        version,
+                        Operator::new(type_id)?,
+                    )?);
+                    None
+                }
+            };
+            // Attach each completed packet to its parent and complete parents in turn
+            loop {
+                let frame = match stack.last_mut() {
+                    Some(frame) => frame,
+                    None => return completed.context("no packet has been parsed"),
+                };
+                if let Some(packet) = completed.take() {
+                    frame.children.push(packet);
+                }
+                if !frame.is_complete(stream.bit_position())? {
+                    break;
+                }
+                completed = stack.pop().map(OperatorFrame::into_packet);
+            }
+        }
+    }
+
+    /// Same as [`Packet::from_stream_iterative`] but also returns the number of bits
+    /// consumed from the [`InputStream`] by the packet (including all of its subpackets).
+    fn from_stream_sized<R: BufRead>(stream: &mut InputStream<R>) -> anyhow::Result<(Self, usize)> {
+        let start = stream.bit_position();
+        let packet = Packet::from_stream_iterative(stream)?;
+        Ok((packet, stream.bit_position() - start))
+    }
+
+    /// Evaluates the expression described by the packet,
+    /// short-circuiting upon the first error found in any subpacket.
     ///
     /// # Implementation Note
     /// This method did not utilize [`Packet::reduce`] for two main reasons:
     /// -  [`Packet::reduce`] did not provide short-circuiting,
     ///    especially in cases when fallible result could happen
     /// -  This method reflects the original purpose of the existence of the [`Packet`]
+    ///
+    /// Operator packets whose subpackets are being evaluated are kept on an explicit stack
+    /// (together with the values of subpackets evaluated so far),
+    /// so that deeply nested packets can be evaluated within bounded call stack space.
     fn eval(&self) -> anyhow::Result<u64> {
-        match &self.payload {
-            Payload::Literal(value) => Ok(*value),
-            Payload::Operation(op, children) => {
-                let children: Vec<_> = children
-                    .iter()
-                    .map(|subpacket| subpacket.eval())
-                    .try_collect()?;
-                op.apply(children.as_slice())
+        let mut stack: Vec<(Operator, std::slice::Iter<Packet>, Vec<u64>)> = Vec::new();
+        let mut next = Some(self);
+        loop {
+            let mut value = match next.take().map(|packet| &packet.payload) {
+                Some(Payload::Literal(value)) => Some(*value),
+                Some(Payload::Operation(op, children)) => {
+                    stack.push((*op, children.iter(), Vec::with_capacity(children.len())));
+                    None
+                }
+                None => None,
+            };
+            // Pass each evaluated value to its parent and evaluate parents in turn
+            loop {
+                let (_, children, values) = match stack.last_mut() {
+                    Some(frame) => frame,
+                    None => return value.context("no packet has been evaluated"),
+                };
+                values.extend(value.take());
+                if let Some(subpacket) = children.next() {
+                    next = Some(subpacket);
+                    break;
+                }
+                let (op, _, values) = stack.pop().unwrap();
+                value = Some(op.apply(values.as_slice())?);
             }
         }
     }
@@ -126,15 +241,58 @@ impl Packet {
     /// based on the following two input arguments:
     /// -  The packet itself, and
     /// -  The slice of reduced values from each subpacket.
+    ///
+    /// # Implementation Note
+    /// Packets are collected in pre-order by [`Packet::visit`] and then reduced in reverse,
+    /// so that every subpacket is reduced before its parent without recursion.
+    /// Reduced values of the subpackets of a packet are then found at the top of the value stack,
+    /// with the value of the first subpacket on top.
     fn reduce<T, F>(&self, func: &F) -> T
     where
         F: Fn(&Self, &[T]) -> T,
     {
-        let children: Vec<_> = match &self.payload {
-            Payload::Literal(_) => Vec::new(),
-            Payload::Operation(_, children) => children.iter().map(|sp| sp.reduce(func)).collect(),
-        };
-        func(self, children.as_slice())
+        let mut packets = Vec::new();
+        self.visit(|packet| packets.push(packet));
+        let mut values: Vec<T> = Vec::new();
+        for packet in packets.into_iter().rev() {
+            let child_count = match &packet.payload {
+                Payload::Literal(_) => 0,
+                Payload::Operation(_, children) => children.len(),
+            };
+            let mut children = values.split_off(values.len() - child_count);
+            children.reverse();
+            values.push(func(packet, children.as_slice()));
+        }
+        values.pop().unwrap()
+    }
+
+    /// Visits every packet in the tree structure in pre-order,
+    /// i.e. each packet is visited before its subpackets (which are visited in order).
+    /// The traversal keeps an explicit stack and hence works for deeply nested packets.
+    fn visit<'a, F: FnMut(&'a Packet)>(&'a self, mut f: F) {
+        let mut stack = vec![self];
+        while let Some(packet) = stack.pop() {
+            f(packet);
+            if let Payload::Operation(_, children) = &packet.payload {
+                stack.extend(children.iter().rev());
+            }
+        }
+    }
+}
+
+impl Drop for Packet {
+    /// Moves all subpackets onto an explicit stack before dropping them one by one,
+    /// since the derived drop glue would recurse once per nesting level.
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        if let Payload::Operation(_, children) = &mut self.payload {
+            stack.append(children);
+        }
+        while let Some(mut packet) = stack.pop() {
+            if let Payload::Operation(_, children) = &mut packet.payload {
+                stack.append(children);
+            }
+        }
     }
 }
 
@@ -149,7 +307,7 @@ enum Payload {
 
 impl Payload {
     /// Parses [`Payload::Literal`] by consuming the next few bits from the stream.
-    fn parse_literal<R: Read>(stream: &mut InputStream<R>) -> anyhow::Result<Payload> {
+    fn parse_literal<R: BufRead>(stream: &mut InputStream<R>) -> anyhow::Result<Payload> {
         let mut bits = Vec::new();
         loop {
             let batch: [_; 5] = stream.fetch()?;
@@ -158,13 +316,14 @@ impl Payload {
                 break;
             }
         }
-        let value = decimal_from_bits(bits.as_slice());
+        let value = bits_to_int(bits.as_slice());
         Ok(Payload::Literal(value))
     }
 
     /// Parses [`Payload::Operation`] by consuming the next few bits from the stream.
     /// This method dispatches to subroutine depending on the length type ID being read next.
-    fn parse_ops<R: Read>(stream: &mut InputStream<R>, op: Operator) -> anyhow::Result<Payload> {
+    #[cfg(test)]
+    fn parse_ops<R: BufRead>(stream: &mut InputStream<R>, op: Operator) -> anyhow::Result<Payload> {
         let [length_type_id] = stream.fetch()?;
         let children = match length_type_id {
             0 => Payload::parse_children_by_bit_length(stream)?,
@@ -177,19 +336,21 @@ impl Payload {
     /// Parses [`Payload::Operation`] by consuming the next few bits from the stream,
     /// already knowing that the length type ID previously read was 0.
     /// Hence, the next 15 bits indicate the total length in bits of sub-packets, etc.
-    fn parse_children_by_bit_length<R: Read>(
+    #[cfg(test)]
+    fn parse_children_by_bit_length<R: BufRead>(
         stream: &mut InputStream<R>,
     ) -> anyhow::Result<Vec<Packet>> {
-        let target_length: usize = decimal_from_bits(stream.fetch::<15>()?.as_slice());
-        let count_start = stream.bits_read;
+        let target_length: usize = bits_to_int(stream.fetch::<15>()?.as_slice());
+        let count_start = stream.bit_position();
+        stream.ensure_within(count_start + target_length)?;
         let mut children = Vec::new();
-        while stream.bits_read < count_start + target_length {
+        while stream.bit_position() < count_start + target_length {
             children.push(Packet::from_stream(stream)?);
         }
         ensure!(
-            stream.bits_read == count_start + target_length,
+            stream.bit_position() == count_start + target_length,
             "too many bits read while parsing subpackets: {} > {}",
-            stream.bits_read - count_start,
+            stream.bit_position() - count_start,
             target_length
         );
         Ok(children)
@@ -198,16 +359,89 @@ impl Payload {
     /// Parses [`Payload::Operation`] by consuming the next few bits from the stream,
     /// already knowing that the length type ID previously read was 1.
     /// Hence, the next 11 bits indicate the number of sub-packets.
-    fn parse_children_by_packet_count<R: Read>(
+    #[cfg(test)]
+    fn parse_children_by_packet_count<R: BufRead>(
         stream: &mut InputStream<R>,
     ) -> anyhow::Result<Vec<Packet>> {
-        let subpacket_count: usize = decimal_from_bits(stream.fetch::<11>()?.as_slice());
+        let subpacket_count: usize = bits_to_int(stream.fetch::<11>()?.as_slice());
         (0..subpacket_count)
             .map(|_| Packet::from_stream(stream))
             .collect()
     }
 }
 
+/// Operator packet whose subpackets are still being parsed
+#[derive(Debug, Clone)]
+struct OperatorFrame {
+    version: u8,
+    op: Operator,
+    /// Condition determining when all subpackets have been parsed
+    length: SubpacketLength,
+    /// Subpackets parsed so far
+    children: Vec<Packet>,
+}
+
+/// Condition determining when all subpackets of an operator packet have been parsed
+#[derive(Debug, Clone, Copy)]
+enum SubpacketLength {
+    /// Length type ID 0: subpackets end exactly at the given number of bits read from the stream
+    BitsReadUntil(usize),
+    /// Length type ID 1: the given number of subpackets
+    PacketCount(usize),
+}
+
+impl OperatorFrame {
+    /// Parses the length type ID and the subpacket length by consuming the next few bits from the stream,
+    /// after the version and the type ID of the operator packet have already been read.
+    fn parse<R: BufRead>(
+        stream: &mut InputStream<R>,
+        version: u8,
+        op: Operator,
+    ) -> anyhow::Result<Self> {
+        let [length_type_id] = stream.fetch()?;
+        let length = match length_type_id {
+            0 => {
+                let target_length: usize = bits_to_int(stream.fetch::<15>()?.as_slice());
+                let target = stream.bit_position() + target_length;
+                stream.ensure_within(target)?;
+                SubpacketLength::BitsReadUntil(target)
+            }
+            1 => SubpacketLength::PacketCount(bits_to_int(stream.fetch::<11>()?.as_slice())),
+            _ => unreachable!(),
+        };
+        Ok(OperatorFrame {
+            version,
+            op,
+            length,
+            children: Vec::new(),
+        })
+    }
+
+    /// Checks whether all subpackets have been parsed given the number of bits read so far.
+    fn is_complete(&self, bits_read: usize) -> anyhow::Result<bool> {
+        match self.length {
+            SubpacketLength::BitsReadUntil(target) => {
+                ensure!(
+                    bits_read <= target,
+                    "too many bits read while parsing subpackets: stream position {} > {}",
+                    bits_read,
+                    target
+                );
+                Ok(bits_read == target)
+            }
+            SubpacketLength::PacketCount(count) => Ok(self.children.len() == count),
+        }
+    }
+
+    /// Converts the fully parsed operator frame into a packet.
+    fn into_packet(self) -> Packet {
+        Packet {
+            version: self.version,
+            payload: Payload::Operation(self.op, self.children),
+        }
+    }
+}
+
 /// Packet in BITS transmission
 #[derive(Debug, Clone, Copy)]
 enum Operator {
@@ -269,27 +503,148 @@ impl Operator {
     }
 }
 
-/// Converts a hexadecimal character into an array of four bits in MSB-first order.
-/// Each bit in the output array is represented by `0` or `1`.
-fn bits_from_hex(c: char) -> anyhow::Result<[Bit; 4]> {
-    let decimal = c
-        .to_digit(16)
-        .map(|d| d as u8)
-        .with_context(|| format!("not a hexadecimal character: '{}'", c.escape_default()))?;
-    Ok([
-        (0b1000 & decimal) / 0b1000,
-        (0b0100 & decimal) / 0b0100,
-        (0b0010 & decimal) / 0b0010,
-        (0b0001 & decimal),
-    ])
-}
+#[cfg(test)]
+mod tests {
+    use aoc2021::bits::int_to_bits;
+
+    use super::*;
 
-/// Converts a sequence of bits arranged in MSB-first order into an integer.
-fn decimal_from_bits<T>(bits: &[Bit]) -> T
-where
-    T: PrimInt,
-{
-    bits.iter().fold(T::zero(), |acc, &bit| {
-        T::from(2).unwrap() * acc + T::from(bit).unwrap()
-    })
+    /// Parses the transmission given as a hexadecimal string into a packet
+    /// together with the number of bits consumed.
+    fn parse_hex(transmission: &str) -> anyhow::Result<(Packet, usize)> {
+        let mut stream =
+            InputStream::new(transmission.as_bytes()).with_max_bits(4 * transmission.len());
+        Packet::from_stream_sized(&mut stream)
+    }
+
+    /// Encodes the sequence of bits into a hexadecimal string, padding zeros at the end.
+    fn bits_to_hex(bits: &[u8]) -> String {
+        bits.chunks(4)
+            .map(|chunk| {
+                let mut nibble = chunk.to_vec();
+                nibble.resize(4, 0);
+                std::char::from_digit(bits_to_int(nibble.as_slice()), 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn visit_nested_transmission() {
+        let (packet, _) = parse_hex("C0015000016115A2E0802F182340").unwrap();
+        let mut packet_count = 0;
+        let mut version_sum = 0;
+        packet.visit(|subpacket| {
+            packet_count += 1;
+            version_sum += subpacket.version as u64;
+        });
+        assert_eq!(packet_count, 7);
+        assert_eq!(version_sum, 23);
+    }
+
+    #[test]
+    fn reduce_nested_transmission() {
+        let (packet, _) = parse_hex("C0015000016115A2E0802F182340").unwrap();
+        let packet_count =
+            packet.reduce(&|_, children: &[usize]| 1 + children.iter().sum::<usize>());
+        assert_eq!(packet_count, 7);
+        let version_sum = packet.reduce(&|subpacket, children: &[u64]| {
+            subpacket.version as u64 + children.iter().sum::<u64>()
+        });
+        assert_eq!(version_sum, 23);
+        // Literal values in the order of subpackets
+        let literals = packet.reduce(
+            &|subpacket, children: &[Vec<u64>]| match subpacket.payload {
+                Payload::Literal(value) => vec![value],
+                Payload::Operation(..) => children.concat(),
+            },
+        );
+        assert_eq!(literals, vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn literal_packet_bit_length() {
+        let (packet, bits_read) = parse_hex("D2FE28").unwrap();
+        assert!(matches!(packet.payload, Payload::Literal(2021)));
+        assert_eq!(bits_read, 21);
+    }
+
+    #[test]
+    fn subpacket_bits_exceed_stream() {
+        // Sum operator packet claiming 1000 bits of subpackets within a 24-bit transmission
+        let err = parse_hex("200FA0").unwrap_err();
+        assert_eq!(err.to_string(), "stream exceeded 24 bits");
+    }
+
+    #[test]
+    fn recursive_same_as_iterative() {
+        let transmissions = [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "C0015000016115A2E0802F182340",
+            "9C0141080250320F1802104A08",
+        ];
+        for transmission in transmissions {
+            let (iterative, _) = parse_hex(transmission).unwrap();
+            let mut stream = InputStream::new(transmission.as_bytes());
+            let recursive = Packet::from_stream(&mut stream).unwrap();
+            assert_eq!(format!("{:?}", recursive), format!("{:?}", iterative));
+        }
+    }
+
+    #[test]
+    fn eval_transmissions() {
+        let expected = [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ];
+        for (transmission, value) in expected {
+            let (packet, _) = parse_hex(transmission).unwrap();
+            assert_eq!(packet.eval().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_packet() {
+        // Each operator packet is a sum with exactly one subpacket (length type ID 1)
+        // and the innermost packet is a literal packet of value 42.
+        // Parsing, visiting, evaluating and dropping must not recurse at this depth.
+        let depth = 100_000;
+        let mut bits = Vec::new();
+        for _ in 0..depth {
+            bits.extend(int_to_bits(1_u8, 3));
+            bits.extend(int_to_bits(0_u8, 3));
+            bits.push(1);
+            bits.extend(int_to_bits(1_u16, 11));
+        }
+        bits.extend(int_to_bits(2_u8, 3));
+        bits.extend(int_to_bits(4_u8, 3));
+        bits.extend([1, 0, 0, 1, 0]);
+        bits.extend([0, 1, 0, 1, 0]);
+        let (packet, _) = parse_hex(bits_to_hex(&bits).as_str()).unwrap();
+
+        let mut nesting = 0;
+        let mut innermost = &packet;
+        while let Payload::Operation(_, children) = &innermost.payload {
+            assert_eq!(children.len(), 1);
+            innermost = &children[0];
+            nesting += 1;
+        }
+        assert_eq!(nesting, depth);
+        assert!(matches!(innermost.payload, Payload::Literal(42)));
+        assert_eq!(packet.eval().unwrap(), 42);
+        let version_sum = packet.reduce(&|subpacket, children: &[u64]| {
+            subpacket.version as u64 + children.iter().sum::<u64>()
+        });
+        assert_eq!(version_sum, depth as u64 + 2);
+    }
 }
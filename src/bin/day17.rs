@@ -1,17 +1,17 @@
 //! Day 17: Trick Shot, Advent of Code 2021  
 //! <https://adventofcode.com/2021/day/17>
-use std::fmt::Display;
 use std::io::{BufRead, BufReader};
-use std::ops::RangeInclusive;
 
-use anyhow::{ensure, Context};
+use anyhow::Context;
 use clap::Parser;
 use itertools::iproduct;
 use lazy_static::lazy_static;
-use num::PrimInt;
+use num::{PrimInt, Signed};
 use regex::Regex;
 
 use aoc2021::argparser::Cli;
+use aoc2021::geometry::Rect;
+use aoc2021::parsing::captures_strict;
 
 /// Main program
 fn main() {
@@ -23,20 +23,23 @@ fn main() {
     let velocity_ranges = feasible_velocities(target).expect("unbounded feasible velocities");
 
     // Part 1: Highest point while hitting the testing range
-    let p1_answer = {
-        let (_, vy) = solve_highest_peak(target, velocity_ranges);
-        peak_distance(vy)
-    };
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            let (_, vy) = solve_highest_peak(target, velocity_ranges);
+            peak_distance(vy)
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Count all possible trajectories
-    let p2_answer = {
-        let (vx_range, vy_range) = velocity_ranges.as_range_inclusive();
-        iproduct!(vx_range, vy_range)
-            .filter(|&(vx, vy)| test_simulate(target, vx, vy))
-            .count()
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            feasible_launches(target)
+                .expect("unbounded feasible velocities")
+                .len()
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -59,8 +62,7 @@ impl Input {
             .unwrap();
         }
         let line = reader.lines().next().context("missing first line")??;
-        let captures = RE
-            .captures(line.as_str())
+        let captures = captures_strict(&RE, line.as_str())
             .with_context(|| format!("invalid line input: {}", line))?;
         let target = Rect::new(
             captures[4].parse()?,
@@ -72,64 +74,6 @@ impl Input {
     }
 }
 
-/// Represents a bounded rectangular area
-#[derive(Debug, Clone, Copy)]
-struct Rect<T> {
-    /// Inclusive lower bound on (horizontal) x-value
-    horz_lower: T,
-    /// Inclusive upper bound on (horizontal) x-value
-    horz_upper: T,
-    /// Inclusive lower bound on (vertical) y-value
-    vert_lower: T,
-    /// Inclusive upper bound on (vertical) y-value
-    vert_upper: T,
-}
-
-impl<T> Rect<T> {
-    /// Construct a new bounded rectangular area
-    fn new(vert_upper: T, horz_upper: T, vert_lower: T, horz_lower: T) -> anyhow::Result<Self>
-    where
-        T: PrimInt + Display,
-    {
-        ensure!(
-            horz_lower <= horz_upper,
-            "conflicting horizontal range: {} > {}",
-            horz_lower,
-            horz_upper,
-        );
-        ensure!(
-            vert_lower < vert_upper,
-            "conflicting vertical range: {} > {}",
-            vert_lower,
-            vert_upper,
-        );
-        Ok(Rect {
-            horz_lower,
-            horz_upper,
-            vert_lower,
-            vert_upper,
-        })
-    }
-
-    /// Obtains the rectangle area as a pair of horizontal and vertical [`RangeInclusive`]
-    fn as_range_inclusive(&self) -> (RangeInclusive<T>, RangeInclusive<T>)
-    where
-        T: PrimInt,
-    {
-        let horz_range = RangeInclusive::new(self.horz_lower, self.horz_upper);
-        let vert_range = RangeInclusive::new(self.vert_lower, self.vert_upper);
-        (horz_range, vert_range)
-    }
-
-    /// Whether the rectangle area contains the given point
-    fn contains(&self, x: T, y: T) -> bool
-    where
-        T: PrimInt,
-    {
-        self.horz_lower <= x && x <= self.horz_upper && self.vert_lower <= y && y <= self.vert_upper
-    }
-}
-
 /// Calculates the tight bound for integer-value, feasible starting velocities
 /// for the probe which would eventually hit the specified rectangular target.
 /// Bounds for horizontal and vertical velocities are determined independently.
@@ -172,6 +116,20 @@ fn feasible_velocities(target: Rect<i64>) -> Option<Rect<i64>> {
     Some(Rect::new(vy_upper, vx_upper, vy_lower, vx_lower).unwrap())
 }
 
+/// Finds all integer-value starting velocities `(vx, vy)` for the probe
+/// which would eventually hit the specified rectangular target.
+/// If the solution is unbounded, then this function would return `None` instead:
+/// a target straddling the level `y = 0` may be hit by a probe which stalls horizontally
+/// above it and falls straight through, no matter how high it was launched,
+/// so there is no finite list of launches to return.
+fn feasible_launches(target: Rect<i64>) -> Option<Vec<(i64, i64)>> {
+    let (vx_range, vy_range) = feasible_velocities(target)?.as_range_inclusive();
+    let launches = iproduct!(vx_range, vy_range)
+        .filter(|&(vx, vy)| test_simulate(target, vx, vy))
+        .collect();
+    Some(launches)
+}
+
 /// Minimum velocity required to at least reach a certain (positive) distance.
 /// This functions provides a tighter lower bound for velocity search space than just velocity 0.
 fn min_velocity_to_reach(dist: i64) -> i64 {
@@ -199,17 +157,79 @@ fn solve_highest_peak(target: Rect<i64>, velocity_range: Rect<i64>) -> (i64, i64
 
 /// Runs the simulation to see whether the provided x- and y-velocity
 /// would make the probe hit the target within the specified range.
-fn test_simulate(target: Rect<i64>, mut vx: i64, mut vy: i64) -> bool {
-    let mut x = 0;
-    let mut y = 0;
-    while vy >= 0 || y > target.vert_lower {
-        x += vx;
-        y += vy;
-        vx -= vx.signum();
-        vy -= 1;
-        if target.contains(x, y) {
-            return true;
+fn test_simulate<T>(target: Rect<T>, vx: T, vy: T) -> bool
+where
+    T: PrimInt + Signed,
+{
+    simulate_trajectory(vx, vy, target.vert_lower).any(|(x, y)| target.contains(x, y))
+}
+
+/// An iterator over successive positions of the probe launched from the origin
+/// with the provided x- and y-velocity. The iterator stops once the probe is
+/// no longer above the `floor` level while it is descending (i.e. it could never rise again).
+fn simulate_trajectory<T>(mut vx: T, mut vy: T, floor: T) -> impl Iterator<Item = (T, T)>
+where
+    T: PrimInt + Signed,
+{
+    let mut x = T::zero();
+    let mut y = T::zero();
+    std::iter::from_fn(move || {
+        if vy < T::zero() && y <= floor {
+            return None;
         }
+        x = x + vx;
+        y = y + vy;
+        vx = vx - vx.signum();
+        vy = vy - T::one();
+        Some((x, y))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/day17_sample.txt");
+
+    #[test]
+    fn parse_target_trailing_junk() {
+        let Input { target } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(target.as_range_inclusive(), (20..=30, -10..=-5));
+        let input = "target area: x=20..30, y=-10..-5 JUNK";
+        let err = Input::from_buffer(input.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), format!("invalid line input: {}", input));
+    }
+
+    #[test]
+    fn feasible_launches_sample() {
+        let Input { target } = Input::from_buffer(SAMPLE.as_bytes()).unwrap();
+        let launches = feasible_launches(target).unwrap();
+        assert_eq!(launches.len(), 112);
+        assert!(launches.contains(&(6, 9)));
+        assert!(launches.contains(&(30, -10)));
+    }
+
+    #[test]
+    fn simulate_trajectory_until_floor() {
+        let positions: Vec<_> = simulate_trajectory(7, 2, -10).collect();
+        assert_eq!(
+            positions,
+            [
+                (7, 2),
+                (13, 3),
+                (18, 3),
+                (22, 2),
+                (25, 0),
+                (27, -3),
+                (28, -7),
+                (28, -12),
+            ]
+        );
+    }
+
+    #[test]
+    fn feasible_launches_unbounded() {
+        let target = Rect::new(5, 8, -5, 3).unwrap();
+        assert!(feasible_launches(target).is_none());
     }
-    false
 }
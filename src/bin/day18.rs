@@ -5,17 +5,11 @@ use std::io::{BufRead, BufReader};
 use std::iter::once;
 use std::ops::Add;
 
-use anyhow::anyhow;
 use clap::Parser;
 use itertools::{chain, Itertools};
-use lazy_static::lazy_static;
 
 use aoc2021::argparser::Cli;
-use aoc2021::snailfish::{Node, SnailfishParser};
-
-lazy_static! {
-    static ref PARSER: SnailfishParser = SnailfishParser::new();
-}
+use aoc2021::snailfish::Node;
 
 /// Main program
 fn main() {
@@ -27,23 +21,28 @@ fn main() {
     let numbers = numbers.iter().map(SerializedSnailfish::from).collect_vec();
 
     // Part 1: Sum of all numbers
-    let p1_answer = {
-        let result = numbers[1..]
-            .iter()
-            .fold(numbers[0].clone(), |acc, n| (&acc + n).reduce());
+    if cli.part() != Some(2) {
+        let result = cli.time_part("Part 1", || {
+            numbers[1..]
+                .iter()
+                .fold(numbers[0].clone(), |acc, n| (&acc + n).reduce())
+        });
         println!("Final result: {}", result);
-        result.magnitude()
-    };
-    println!("Part 1 answer: {}", p1_answer);
+        println!("Part 1 answer: {}", result.magnitude());
+    }
 
     // Part 2: Largest sum of a pair
-    let p2_answer = numbers
-        .iter()
-        .permutations(2)
-        .map(|v| (v[0] + v[1]).reduce().magnitude())
-        .max()
-        .expect("empty seq of numbers");
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            numbers
+                .iter()
+                .permutations(2)
+                .map(|v| (v[0] + v[1]).reduce().magnitude())
+                .max()
+                .expect("empty seq of numbers")
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -58,12 +57,7 @@ impl Input {
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
         let mut numbers = Vec::new();
         for line in reader.lines() {
-            let line = line?;
-            numbers.push(
-                PARSER
-                    .parse(line.as_str())
-                    .map_err(|_| anyhow!("cannot parse line: '{}'", line.escape_default()))?,
-            )
+            numbers.push(line?.parse()?)
         }
         Ok(Input { numbers })
     }
@@ -77,21 +71,19 @@ impl SerializedSnailfish {
     /// Obtains the reduced form of the snailfish itself.
     fn reduce(&self) -> Self {
         let mut fish = self.clone();
-        loop {
-            if let Some(exploded_fish) = fish.explode() {
-                fish = exploded_fish;
-                continue;
-            }
-            if let Some(splitted_fish) = fish.split() {
-                fish = splitted_fish;
-                continue;
-            }
-            return fish;
-        }
+        fish.reduce_in_place();
+        fish
     }
 
-    /// Explode the snailfish itself, if possible.
-    fn explode(&self) -> Option<Self> {
+    /// Reduces the snailfish in-place by repeatedly exploding or splitting
+    /// directly on the backing vector of elements.
+    fn reduce_in_place(&mut self) {
+        while self.explode_in_place() || self.split_in_place() {}
+    }
+
+    /// Explodes the snailfish in-place, if possible.
+    /// Returns whether the explosion took place.
+    fn explode_in_place(&mut self) -> bool {
         let mut level: usize = 0;
         let mut pivot = None;
         for (pos, elem) in self.0.iter().enumerate() {
@@ -105,53 +97,53 @@ impl SerializedSnailfish {
                 _ => (),
             }
         }
-        pivot.map(|pos| {
-            let (fst, snd) = match self.0[pos..pos+4] {
-                [Element::LBracket, Element::Value(fst), Element::Value(snd), Element::RBracket] => (fst, snd),
-                _ => panic!("invalid serialization of snailfish number"),
-            };
-            let elements = chain!(self.0[..pos].iter(), once(&Element::Value(0)), self.0[pos+4..].iter());
-            let mut elements = elements.copied().collect_vec();
-            for elem in elements[..pos].iter_mut().rev() {
-                if elem.is_value() {
-                    *elem = elem.map(|v| v + fst);
-                    break;
-                }
+        let pos = match pivot {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let (fst, snd) = match self.0[pos..pos + 4] {
+            [Element::LBracket, Element::Value(fst), Element::Value(snd), Element::RBracket] => {
+                (fst, snd)
             }
-            for elem in elements[pos+1..].iter_mut() {
-                if elem.is_value() {
-                    *elem = elem.map(|v| v + snd);
-                    break;
-                }
+            _ => panic!("invalid serialization of snailfish number"),
+        };
+        self.0.splice(pos..pos + 4, once(Element::Value(0)));
+        for elem in self.0[..pos].iter_mut().rev() {
+            if elem.is_value() {
+                *elem = elem.map(|v| v + fst);
+                break;
             }
-            SerializedSnailfish(elements)
-        })
+        }
+        for elem in self.0[pos + 1..].iter_mut() {
+            if elem.is_value() {
+                *elem = elem.map(|v| v + snd);
+                break;
+            }
+        }
+        true
     }
 
-    /// Split the snailfish itself, if possible.
-    fn split(&self) -> Option<Self> {
+    /// Splits the snailfish in-place, if possible.
+    /// Returns whether the split took place.
+    fn split_in_place(&mut self) -> bool {
         let pivot = self
             .0
             .iter()
             .find_position(|elem| matches!(elem, Element::Value(v) if *v >= 10));
-        pivot.map(|(pos, elem)| {
-            let value = elem.unwrap_value();
-            let fst = value / 2;
-            let snd = value - fst;
-            let new_elements = [
-                Element::LBracket,
-                Element::Value(fst),
-                Element::Value(snd),
-                Element::RBracket,
-            ];
-            let elements = chain!(
-                self.0[..pos].iter(),
-                new_elements.iter(),
-                self.0[pos + 1..].iter()
-            );
-            let elements = elements.copied().collect_vec();
-            SerializedSnailfish(elements)
-        })
+        let (pos, value) = match pivot {
+            Some((pos, elem)) => (pos, elem.unwrap_value()),
+            None => return false,
+        };
+        let fst = value / 2;
+        let snd = value - fst;
+        let new_elements = [
+            Element::LBracket,
+            Element::Value(fst),
+            Element::Value(snd),
+            Element::RBracket,
+        ];
+        self.0.splice(pos..pos + 1, new_elements);
+        true
     }
 
     /// Magnitude of the snailfish
@@ -260,3 +252,87 @@ impl Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    /// Minimal seeded pseudo-random number generator (xorshift64*) for reproducible tests
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next_below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) % bound
+        }
+    }
+
+    /// Generates a random snailfish number whose pairs are nested at most `max_depth` deep.
+    fn random_snailfish(rng: &mut XorShift, max_depth: usize) -> Node {
+        if max_depth == 0 || rng.next_below(3) == 0 {
+            Node::Leaf(rng.next_below(16) as i64)
+        } else {
+            Node::Branch(
+                Box::new(random_snailfish(rng, max_depth - 1)),
+                Box::new(random_snailfish(rng, max_depth - 1)),
+            )
+        }
+    }
+
+    const SAMPLE_INPUT: &str = include_str!("../../inputs/day18_large_sample.txt");
+
+    fn serialize(s: &str) -> SerializedSnailfish {
+        SerializedSnailfish::from(&s.parse::<Node>().unwrap())
+    }
+
+    #[test_case("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"; "explode leftmost")]
+    #[test_case("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"; "explode rightmost")]
+    #[test_case("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"; "explode middle")]
+    #[test_case("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]", "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"; "explode twice")]
+    fn in_place_reduction(s: &str, expected: &str) {
+        let mut fish = serialize(s);
+        fish.reduce_in_place();
+        assert_eq!(fish.to_string(), expected);
+    }
+
+    #[test]
+    fn in_place_reduction_sum() {
+        let sum = &serialize("[[[[4,3],4],4],[7,[[8,4],9]]]") + &serialize("[1,1]");
+        let mut fish = sum.clone();
+        fish.reduce_in_place();
+        assert_eq!(fish.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+        assert_eq!(sum.reduce().to_string(), fish.to_string());
+    }
+
+    #[test]
+    fn homework_sum_sample() {
+        let Input { numbers } = Input::from_buffer(SAMPLE_INPUT.as_bytes()).unwrap();
+        let numbers = numbers.iter().map(SerializedSnailfish::from).collect_vec();
+        let result = numbers[1..]
+            .iter()
+            .fold(numbers[0].clone(), |acc, n| (&acc + n).reduce());
+        assert_eq!(
+            result.to_string(),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+        );
+        assert_eq!(result.magnitude(), 4140);
+    }
+
+    #[test]
+    fn tree_reduction_same_as_serialized() {
+        let mut rng = XorShift(0x5EED_2021_0018);
+        for _ in 0..500 {
+            let fst = random_snailfish(&mut rng, 4);
+            let snd = random_snailfish(&mut rng, 4);
+            let serialized =
+                (&SerializedSnailfish::from(&fst) + &SerializedSnailfish::from(&snd)).reduce();
+            let tree = Node::Branch(Box::new(fst), Box::new(snd)).reduce();
+            assert_eq!(tree.magnitude(), serialized.magnitude());
+            assert_eq!(tree.to_string(), serialized.to_string());
+        }
+    }
+}
@@ -6,14 +6,15 @@ use std::io::{BufRead, BufReader};
 
 use anyhow::{anyhow, bail, ensure, Context};
 use clap::Parser;
-use itertools::{iproduct, Itertools};
+use itertools::{iproduct, izip, Itertools};
 use lazy_static::lazy_static;
 use num::Zero;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use regex::Regex;
 
 use aoc2021::argparser::Cli;
-use aoc2021::collect_array::CollectArray;
-use aoc2021::hashing::HashSet;
+use aoc2021::hashing::{Counter, HashSet};
 use aoc2021::vecmat::{CMatrix, CVector};
 
 /// Represents a point in 3-dimensional space
@@ -21,7 +22,7 @@ type VecPoint = CVector<i64, 3>;
 type TransMatrix = CMatrix<i64, 3, 3>;
 
 lazy_static! {
-    static ref CUBE_ROTATIONS: [TransMatrix; 24] = cube_rotations();
+    static ref CUBE_ROTATIONS: [TransMatrix; 24] = TransMatrix::cube_rotations();
 }
 
 /// Main program
@@ -31,28 +32,75 @@ fn main() {
     let Input { reports } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Reconstruct the locations of scanners and beacons
-    // using the orient and align technique, targeting 12 overlapping beacons
-    let genesis_report = OrientAlignResult {
-        offset: VecPoint::zero(),
-        report: reports[0].clone(),
+    let (beacons, scanners) = cli.time_part("Reconstruct", || reconstruct(&reports));
+
+    // Part 1: Count all beacons
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || beacons.len());
+        println!("Part 1 answer: {}", p1_answer);
+    }
+
+    // Part 2: Furthest pair of scanners
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            max_manhattan_distance(scanners.as_slice()).expect("empty scanner info")
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
+}
+
+/// Reconstructs the locations of all beacons and scanners relative to the first scanner
+/// using the orient and align technique, targeting 12 overlapping beacons.
+/// Returns the full set of beacons together with the positions of all aligned scanners,
+/// both of which are empty if there are no reports.
+/// Alignment attempts against each base report run on multiple threads
+/// when the `parallel` feature is enabled.
+fn reconstruct(reports: &[Report]) -> (HashSet<VecPoint>, Vec<VecPoint>) {
+    let genesis_report = match reports.first() {
+        Some(report) => OrientAlignResult {
+            offset: VecPoint::zero(),
+            report: report.clone(),
+        },
+        None => return (HashSet::default(), Vec::new()),
     };
     let mut base_report_queue = VecDeque::from([genesis_report]);
-    let mut remaining = reports[1..].iter().cloned().collect_vec();
+    let mut remaining = reports[1..]
+        .iter()
+        .map(|report| (report.clone(), report.distance_fingerprint()))
+        .collect_vec();
     let mut beacons = HashSet::default();
     let mut scanners = Vec::new();
 
     // Take a base report from the queue and try to
-    // orient and align all other remaining reports if possible
+    // orient and align all other remaining reports if possible,
+    // skipping those whose fingerprints could not possibly overlap with the base report
     while let Some(base_report) = base_report_queue.pop_front() {
+        let base_fingerprint = base_report.report.distance_fingerprint();
+        let is_candidate = remaining
+            .iter()
+            .map(|(_, fingerprint)| base_fingerprint.intersection_size(fingerprint) >= 12 * 11 / 2)
+            .collect_vec();
+        let candidates = izip!(remaining.iter(), is_candidate.iter())
+            .filter(|(_, &is_candidate)| is_candidate)
+            .map(|((report, _), _)| report.clone())
+            .collect_vec();
+        let mut results =
+            orient_and_align_all(&base_report.report, &candidates, cfg!(feature = "parallel"))
+                .into_iter();
         let mut next_remaining = Vec::new();
-        for report in remaining {
-            if let Some(result) = base_report.report.orient_and_align(&report, 12, 1000) {
+        for ((report, fingerprint), is_candidate) in izip!(remaining, is_candidate) {
+            let result = if is_candidate {
+                results.next().unwrap()
+            } else {
+                None
+            };
+            if let Some(result) = result {
                 base_report_queue.push_back(OrientAlignResult {
                     offset: base_report.offset + result.offset,
                     report: result.report,
                 })
             } else {
-                next_remaining.push(report);
+                next_remaining.push((report, fingerprint));
             }
         }
         let new_beacons = base_report.report.0.into_iter();
@@ -60,17 +108,34 @@ fn main() {
         scanners.push(base_report.offset);
         remaining = next_remaining;
     }
+    (beacons, scanners)
+}
 
-    // Part 1: Count all beacons
-    let p1_answer = beacons.len();
-    println!("Part 1 answer: {}", p1_answer);
-
-    // Part 2: Furthest pair of scanners
-    let p2_answer = iproduct!(scanners.iter().copied(), scanners.iter().copied())
+/// Computes the largest manhattan distance between any two of the given scanners,
+/// or `None` if there are no scanners.
+fn max_manhattan_distance(scanners: &[VecPoint]) -> Option<i64> {
+    iproduct!(scanners.iter().copied(), scanners.iter().copied())
         .map(|(a, b)| (a - b).norm1())
         .max()
-        .expect("empty scanner info");
-    println!("Part 2 answer: {}", p2_answer);
+}
+
+/// Attempts to orient and align each of the `candidates` reports against the `base` report,
+/// producing the results in the same order as the candidates.
+/// Attempts run on the rayon thread pool if `parallel` is set and the `parallel` feature is enabled,
+/// otherwise they run sequentially.
+fn orient_and_align_all(
+    base: &Report,
+    candidates: &[Report],
+    parallel: bool,
+) -> Vec<Option<OrientAlignResult>> {
+    let align = |report: &Report| base.orient_and_align(report, 12, 1000);
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return candidates.par_iter().map(align).collect();
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel; // falls back to sequential attempts without the feature
+    candidates.iter().map(align).collect()
 }
 
 /// Program input data
@@ -146,19 +211,14 @@ impl Input {
             .unwrap();
         }
         let captures = COORDS_RE.captures(s)?;
-        let x = match captures[1].parse() {
-            Ok(value) => value,
-            _ => return Some(Err(anyhow!("cannot parse integer"))),
-        };
-        let y = match captures[2].parse() {
-            Ok(value) => value,
-            _ => return Some(Err(anyhow!("cannot parse integer"))),
-        };
-        let z = match captures[3].parse() {
-            Ok(value) => value,
-            _ => return Some(Err(anyhow!("cannot parse integer"))),
-        };
-        Some(Ok(CVector::new([x, y, z])))
+        let coords: anyhow::Result<Vec<i64>> = (1..=3)
+            .map(|i| {
+                captures[i]
+                    .parse()
+                    .map_err(|_| anyhow!("cannot parse integer"))
+            })
+            .collect();
+        Some(coords.and_then(|coords| CVector::from_slice(&coords)))
     }
 }
 
@@ -177,10 +237,25 @@ impl Report {
         self.0.push(point);
     }
 
+    /// Computes the multiset of squared euclidean distances between all pairs of beacons in the report.
+    /// This fingerprint is invariant under rotation and translation, so two reports
+    /// sharing `n` beacons must have at least `n * (n - 1) / 2` distances in common
+    /// (counting repeated distances with multiplicity).
+    fn distance_fingerprint(&self) -> Counter<i64> {
+        self.0
+            .iter()
+            .tuple_combinations()
+            .map(|(&p, &q)| {
+                let d = p - q;
+                d.values().map(|v| v * v).sum()
+            })
+            .collect()
+    }
+
     /// Makes a copy of the report by transforming positions of the beacons
     /// using the specified transformation matrix.
     fn rotate_copy(&self, mat: TransMatrix) -> Self {
-        Report(self.0.iter().copied().map(|p| mat * p).collect_vec())
+        Report(mat.transform_all(&self.0))
     }
 
     /// Attempts to rotate the `other` scanner report and aligns its reported beacons with _this_ scanner.
@@ -231,13 +306,13 @@ impl Report {
         let fst_set = self.0.iter().copied();
         let fst_set = fst_set
             .filter(|p| (*p - offset).norm_max() <= scanner_range)
-            .sorted_by_key(|p| p.to_vec())
+            .sorted_by_key(|p| p.as_key())
             .collect_vec();
         let snd_set = other.0.iter().copied();
         let snd_set = snd_set
             .map(|p| p + offset)
             .filter(|p| p.norm_max() <= scanner_range)
-            .sorted_by_key(|p| p.to_vec())
+            .sorted_by_key(|p| p.as_key())
             .collect_vec();
         fst_set == snd_set
     }
@@ -252,18 +327,58 @@ struct OrientAlignResult {
     report: Report,
 }
 
-/// Generates all transformation matrix which would rotate
-/// an axis-aligned cube centered at the origin in all 24 possible ways.
-fn cube_rotations() -> [CMatrix<i64, 3, 3>; 24] {
-    let xyz_rotate_suite = TransMatrix::xyz_rotate_suite();
-    let xy_rotate_suite = TransMatrix::xy_rotate_suite();
-    let z_rotate_suite = TransMatrix::z_rotate_suite();
-    iproduct!(
-        xyz_rotate_suite.iter().copied(),
-        xy_rotate_suite.iter().copied(),
-        z_rotate_suite.iter().copied()
-    )
-    .map(|(a, b, c)| c * b * a)
-    .collect_exact()
-    .unwrap()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../inputs/day19_large_sample.txt");
+
+    #[test]
+    fn reconstruct_sample() {
+        let Input { reports } = Input::from_buffer(SAMPLE_INPUT.as_bytes()).unwrap();
+        let (beacons, scanners) = reconstruct(&reports);
+        assert_eq!(beacons.len(), 79);
+        assert_eq!(scanners.len(), reports.len());
+        assert_eq!(max_manhattan_distance(&scanners), Some(3621));
+    }
+
+    #[test]
+    fn fingerprint_with_repeated_distances() {
+        // Beacons on a lattice share many equal pairwise distances
+        let shared = iproduct!(0..3, 0..4)
+            .map(|(x, y)| VecPoint::new([10 * x, 10 * y, 0]))
+            .collect_vec();
+        let base = Report(shared.clone());
+        let offset = VecPoint::new([5, -7, 11]);
+        let mut other = Report(shared).rotate_copy(CUBE_ROTATIONS[7]);
+        other.0.iter_mut().for_each(|p| *p = *p + offset);
+        other.push(VecPoint::new([123, 456, 789]));
+        let common = base
+            .distance_fingerprint()
+            .intersection_size(&other.distance_fingerprint());
+        assert!(common >= 12 * 11 / 2);
+    }
+
+    #[test]
+    fn reconstruct_empty() {
+        let (beacons, scanners) = reconstruct(&[]);
+        assert!(beacons.is_empty());
+        assert!(scanners.is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_orient_and_align_same_as_sequential() {
+        let Input { reports } = Input::from_buffer(SAMPLE_INPUT.as_bytes()).unwrap();
+        let summarize = |results: Vec<Option<OrientAlignResult>>| {
+            results
+                .into_iter()
+                .map(|result| result.map(|result| (result.offset, result.report.0)))
+                .collect_vec()
+        };
+        let sequential = summarize(orient_and_align_all(&reports[0], &reports[1..], false));
+        let parallel = summarize(orient_and_align_all(&reports[0], &reports[1..], true));
+        assert!(sequential.iter().any(Option::is_some));
+        assert_eq!(sequential, parallel);
+    }
 }
@@ -5,11 +5,13 @@ use std::io::{BufRead, BufReader};
 
 use anyhow::{bail, ensure, Context};
 use clap::Parser;
-use itertools::iproduct;
+use itertools::{iproduct, Itertools};
 
 use aoc2021::argparser::Cli;
+use aoc2021::automaton::step_automaton;
 use aoc2021::collect_array::CollectArray;
-use aoc2021::grid::GridPoint;
+use aoc2021::grid::{add_points, DynGrid, GridPoint};
+use aoc2021::parsing_grid::parse_char_grid;
 
 /// Main program
 fn main() {
@@ -21,21 +23,27 @@ fn main() {
     } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Enhance image twice
-    let p1_answer = {
-        let image = input_image.enhance(&enhancer_table);
-        let image = image.enhance(&enhancer_table);
-        assert!(!image.fallback_pixels);
-        image.on_pixels.len()
-    };
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || {
+            let image = input_image.enhance(&enhancer_table);
+            let image = image.enhance(&enhancer_table);
+            assert!(!image.fallback_pixels);
+            image.on_pixels.len()
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: Enhance image 50 times
-    let p2_answer = {
-        let image = (0..50).fold(input_image, |image, _| image.enhance(&enhancer_table));
-        assert!(!image.fallback_pixels);
-        image.on_pixels.len()
-    };
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || {
+            let image = (0..50).fold(input_image.clone(), |image, _| {
+                image.enhance(&enhancer_table)
+            });
+            assert!(!image.fallback_pixels);
+            image.on_pixels.len()
+        });
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -49,41 +57,26 @@ struct Input {
 
 impl Input {
     /// Parses program input from buffered reader.
-    fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
-        let mut lines = reader.lines();
+    fn from_buffer(mut reader: impl BufRead) -> anyhow::Result<Self> {
         let enhancer_lookup = {
-            let line = lines.next().context("expected first line")??;
-            line.trim()
-                .chars()
-                .map(|c| match c {
-                    '.' => Ok(false),
-                    '#' => Ok(true),
-                    _ => bail!("invalid char: '{}'", c.escape_default()),
-                })
-                .try_collect_exact()?
+            let mut line = String::new();
+            reader.read_line(&mut line).context("expected first line")?;
+            line.trim().chars().map(parse_pixel).try_collect_exact()?
         };
 
-        let break_line = lines.next().context("expected empty second line")??;
+        let mut break_line = String::new();
+        reader
+            .read_line(&mut break_line)
+            .context("expected empty second line")?;
         ensure!(break_line.trim().is_empty(), "expected empty second line");
 
-        let mut on_pixels = HashSet::new();
-        for (i, line) in lines.enumerate() {
-            for (j, c) in line?.trim().chars().enumerate() {
-                if c == '#' {
-                    on_pixels.insert((i as i64, j as i64));
-                }
-            }
-        }
-        let x_values = on_pixels.iter().copied().map(|p| p.0);
-        let x_max = x_values.max().context("empty image")?;
-        let y_values = on_pixels.iter().copied().map(|p| p.1);
-        let y_max = y_values.max().context("empty image")?;
-        let input_image = Image {
-            min_point: (0, 0),
-            max_point: (x_max, y_max),
-            on_pixels,
-            fallback_pixels: false,
-        };
+        let grid = parse_char_grid(reader, parse_pixel)?;
+        let on_pixels: HashSet<_> = grid
+            .indices()
+            .filter(|&pos| grid[pos])
+            .map(|(i, j)| (i as i64, j as i64))
+            .collect();
+        let input_image = Image::from_points(on_pixels);
 
         Ok(Input {
             enhancer_table: enhancer_lookup,
@@ -92,6 +85,15 @@ impl Input {
     }
 }
 
+/// Parses a pixel character into whether the pixel is lit.
+fn parse_pixel(c: char) -> anyhow::Result<bool> {
+    match c {
+        '.' => Ok(false),
+        '#' => Ok(true),
+        _ => bail!("invalid char: '{}'", c.escape_default()),
+    }
+}
+
 /// One possible representation of an image
 #[derive(Debug, Clone)]
 struct Image {
@@ -106,6 +108,24 @@ struct Image {
 }
 
 impl Image {
+    /// Creates an image whose core image region is the bounding box of all light pixels,
+    /// and every pixel outside such region is dark.
+    /// An image without any light pixels has an empty core image region.
+    fn from_points(on_pixels: HashSet<GridPoint<i64>>) -> Self {
+        let x_bounds = on_pixels.iter().map(|p| p.0).minmax().into_option();
+        let y_bounds = on_pixels.iter().map(|p| p.1).minmax().into_option();
+        let (min_point, max_point) = match (x_bounds, y_bounds) {
+            (Some((x_min, x_max)), Some((y_min, y_max))) => ((x_min, y_min), (x_max, y_max)),
+            _ => ((0, 0), (-1, -1)),
+        };
+        Image {
+            min_point,
+            max_point,
+            on_pixels,
+            fallback_pixels: false,
+        }
+    }
+
     /// Gets the boolean state of a pixel of the image
     fn get(&self, index: (i64, i64)) -> bool {
         if self.min_point.0 <= index.0
@@ -120,28 +140,85 @@ impl Image {
     }
 
     /// Enhance an image using the lookup `table` through Image Enhancement Algorithm.
+    /// The core image region grows by one pixel in every direction, and the enhancement
+    /// over such region is carried out as a cellular automaton step.
     fn enhance(&self, enhancer_table: &[bool; 512]) -> Self {
-        let x_min = self.min_point.0 - 1;
-        let y_min = self.min_point.1 - 1;
-        let x_max = self.max_point.0 + 1;
-        let y_max = self.max_point.1 + 1;
-        let on_pixels: HashSet<GridPoint<i64>> = iproduct!(x_min..=x_max, y_min..=y_max)
-            .filter(|pos| {
-                let index = iproduct!(-1..=1, -1..=1).fold(0, |acc, step| {
-                    2 * acc + (self.get((pos.0 + step.0, pos.1 + step.1))) as usize
-                });
-                enhancer_table[index]
+        let min_point = add_points(self.min_point, (-1, -1));
+        let max_point = add_points(self.max_point, (1, 1));
+        let nrows = (max_point.0 - min_point.0 + 1) as usize;
+        let ncols = (max_point.1 - min_point.1 + 1) as usize;
+        let rows = (0..nrows)
+            .map(|i| {
+                (0..ncols)
+                    .map(|j| self.get(add_points(min_point, (i as i64, j as i64))))
+                    .collect()
             })
             .collect();
+        let grid = DynGrid::from_rows(rows).unwrap();
+        let grid = step_automaton(&grid, |(i, j), grid| {
+            let index = iproduct!(-1..=1, -1..=1).fold(0, |acc, (di, dj): (i64, i64)| {
+                let pixel = match (checked_offset(i, di), checked_offset(j, dj)) {
+                    (Some(i), Some(j)) => grid.get((i, j)).copied(),
+                    _ => None,
+                };
+                2 * acc + pixel.unwrap_or(self.fallback_pixels) as usize
+            });
+            enhancer_table[index]
+        });
+        let on_pixels = grid
+            .indices()
+            .filter(|&pos| grid[pos])
+            .map(|(i, j)| add_points(min_point, (i as i64, j as i64)))
+            .collect();
         let fallback_pixels = match self.fallback_pixels {
             true => enhancer_table[511],
             false => enhancer_table[0],
         };
         Image {
-            min_point: (x_min, y_min),
-            max_point: (x_max, y_max),
+            min_point,
+            max_point,
             on_pixels,
             fallback_pixels,
         }
     }
 }
+
+/// Offsets an unsigned grid coordinate by a signed step,
+/// or returns `None` if the result would be negative.
+fn checked_offset(value: usize, step: i64) -> Option<usize> {
+    usize::try_from(value as i64 + step).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points_shifted() {
+        let on_pixels: HashSet<_> = [(-3, 5), (2, -1), (0, 0)].into_iter().collect();
+        let image = Image::from_points(on_pixels);
+        assert_eq!(image.min_point, (-3, -1));
+        assert_eq!(image.max_point, (2, 5));
+        assert!(image.get((-3, 5)));
+        assert!(!image.get((-3, 4)));
+        assert!(!image.get((100, 100)));
+    }
+
+    #[test]
+    fn from_points_empty() {
+        let image = Image::from_points(HashSet::new());
+        assert_eq!(image.min_point, (0, 0));
+        assert_eq!(image.max_point, (-1, -1));
+        assert!(!image.get((0, 0)));
+    }
+
+    #[test]
+    fn enhance_sample() {
+        const S: &str = include_str!("../../inputs/day20_sample.txt");
+        let input = Input::from_buffer(S.as_bytes()).unwrap();
+        let image = (0..2).fold(input.input_image, |image, _| {
+            image.enhance(&input.enhancer_table)
+        });
+        assert_eq!(image.on_pixels.len(), 35);
+    }
+}
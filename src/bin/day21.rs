@@ -3,15 +3,16 @@
 use std::io::{BufRead, BufReader};
 use std::str::FromStr;
 
-use anyhow::{ensure, Context};
+use anyhow::Context;
 use clap::Parser;
 use itertools::{iproduct, Itertools};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use aoc2021::argparser::Cli;
+use aoc2021::ensure_that::EnsureThat;
 use aoc2021::hashing::HashMap;
-use aoc2021::parsing::QuickParse;
+use aoc2021::parsing::{captures_strict, QuickParse};
 
 /// Main program
 fn main() {
@@ -20,21 +21,25 @@ fn main() {
     let Input { player_data } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Deterministic game
-    let part1_answer = {
-        let game_config = GameConfig::new(10, 1000, 3);
-        let game_result =
-            simulate_deterministic_game(&player_data, &game_config, (1..=1000).cycle());
-        game_result.losing_player().score * game_result.total_rolls
-    };
-    println!("Part 1 answer: {}", part1_answer);
+    if cli.part() != Some(2) {
+        let part1_answer = cli.time_part("Part 1", || {
+            let game_config = GameConfig::new(10, 1000, 3);
+            let game_result =
+                simulate_deterministic_game(&player_data, &game_config, (1..=1000).cycle());
+            game_result.losing_player().score * game_result.total_rolls
+        });
+        println!("Part 1 answer: {}", part1_answer);
+    }
 
     // Part 2: Dirac game
-    let part2_answer = {
-        let game_config = GameConfig::new(10, 21, 3);
-        let game_result = simulate_dirac_game(&player_data, &game_config, [1, 2, 3].as_slice());
-        u64::max(game_result.winning_counts[0], game_result.winning_counts[1])
-    };
-    println!("Part 2 answer: {}", part2_answer);
+    if cli.part() != Some(1) {
+        let part2_answer = cli.time_part("Part 2", || {
+            let game_config = GameConfig::new(10, 21, 3);
+            let game_result = simulate_dirac_game(&player_data, &game_config, [1, 2, 3].as_slice());
+            u64::max(game_result.winning_counts[0], game_result.winning_counts[1])
+        });
+        println!("Part 2 answer: {}", part2_answer);
+    }
 }
 
 /// Program input data
@@ -48,16 +53,16 @@ impl Input {
     /// Parses program input from buffered reader.
     fn from_buffer(reader: impl BufRead) -> anyhow::Result<Self> {
         let mut lines = reader.lines();
-        let p1_init_state: PlayerInitState = lines
+        let p1_init_state = lines
             .next()
             .context("expected first line input")??
-            .parse()?;
-        ensure!(p1_init_state.id == 1);
-        let p2_init_state: PlayerInitState = lines
+            .parse::<PlayerInitState>()?
+            .ensure_that_ctx(|s| s.id == 1, "expected player id 1")?;
+        let p2_init_state = lines
             .next()
-            .context("expected first line input")??
-            .parse()?;
-        ensure!(p2_init_state.id == 2);
+            .context("expected second line input")??
+            .parse::<PlayerInitState>()?
+            .ensure_that_ctx(|s| s.id == 2, "expected player id 2")?;
         let player_data = [p1_init_state, p2_init_state];
         Ok(Input { player_data })
     }
@@ -88,11 +93,10 @@ impl FromStr for PlayerInitState {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
             static ref PLAYER_INITIAL_STATE: Regex =
-                Regex::new(r"(?i)player\s+(\d+)\s+starting\s+position:\s*(\d+)").unwrap();
+                Regex::new(r"(?i)\s*player\s+(\d+)\s+starting\s+position:\s*(\d+)\s*").unwrap();
         }
-        let captures = PLAYER_INITIAL_STATE
-            .captures(s)
-            .context("invalid input line format")?;
+        let captures =
+            captures_strict(&PLAYER_INITIAL_STATE, s).context("invalid input line format")?;
         Ok(PlayerInitState {
             id: captures[1].quickparse()?,
             pos: captures[2].quickparse()?,
@@ -319,3 +323,31 @@ fn simulate_dirac_game(
 
     DiracGameResult { winning_counts }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_player_surrounding_whitespace() {
+        for line in [
+            "Player 1 starting position: 4",
+            "Player 1 starting position: 4 ",
+            "  player 1 starting position:4\t",
+        ] {
+            let player: PlayerInitState = line.parse().unwrap();
+            assert_eq!((player.id, player.pos), (1, 4));
+        }
+    }
+
+    #[test]
+    fn parse_player_trailing_junk() {
+        let err = "Player 1 starting position: 4 JUNK"
+            .parse::<PlayerInitState>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid input line format");
+        assert!("Player 1 starting position: 4x"
+            .parse::<PlayerInitState>()
+            .is_err());
+    }
+}
@@ -2,7 +2,7 @@
 //! <https://adventofcode.com/2021/day/22>
 use std::collections::BTreeSet;
 use std::io::{BufRead, BufReader};
-use std::ops::Range;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 use anyhow::{bail, Context};
@@ -12,7 +12,7 @@ use lazy_static::lazy_static;
 use regex::Regex;
 
 use aoc2021::argparser::Cli;
-use aoc2021::parsing::QuickParse;
+use aoc2021::parsing::{captures_strict, QuickParse};
 
 /// Main program
 fn main() {
@@ -21,17 +21,19 @@ fn main() {
     let Input { reboot_steps } = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: Cubes within (-50..50)^3
-    let region = Cuboid {
-        x: Interval::new(-50, 50),
-        y: Interval::new(-50, 50),
-        z: Interval::new(-50, 50),
-    };
-    let p1_answer = on_cubes_in_small_cuboid(reboot_steps.as_slice(), &region);
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let region = Cuboid::cube((0, 0, 0), 50);
+        let p1_answer = cli.time_part("Part 1", || {
+            on_cubes_in_small_cuboid(reboot_steps.as_slice(), &region)
+        });
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: TODO
-    let p2_answer = on_cubes(reboot_steps.as_slice());
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || on_cubes(reboot_steps.as_slice()));
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -72,9 +74,8 @@ impl FromStr for RebootStep {
             )
             .unwrap();
         }
-        let captures = RE
-            .captures(s)
-            .with_context(|| format!("invalid line input: {}", s))?;
+        let captures =
+            captures_strict(&RE, s).with_context(|| format!("invalid line input: {}", s))?;
         let cuboid = Cuboid {
             x: Interval::new(captures[2].parse()?, captures[3].parse()?),
             y: Interval::new(captures[4].parse()?, captures[5].parse()?),
@@ -101,9 +102,39 @@ struct Cuboid {
 }
 
 impl Cuboid {
-    /// Volume of the cuboid
-    fn volume(&self) -> i64 {
-        self.x.len() * self.y.len() * self.z.len()
+    /// Creates a cube spanning `radius` units from `center` in every direction, inclusive
+    fn cube(center: (i64, i64, i64), radius: i64) -> Self {
+        let (x, y, z) = center;
+        Cuboid {
+            x: Interval::centered(x, radius),
+            y: Interval::centered(y, radius),
+            z: Interval::centered(z, radius),
+        }
+    }
+
+    /// Volume of the cuboid, which may exceed the range of `i64` for huge cuboids
+    fn volume(&self) -> i128 {
+        self.x.len() as i128 * self.y.len() as i128 * self.z.len() as i128
+    }
+
+    /// An iterator over all integer points inside the cuboid.
+    /// Points are produced lazily, but iterating through a huge cuboid
+    /// would still take time proportional to its volume.
+    fn points(&self) -> impl Iterator<Item = (i64, i64, i64)> {
+        iproduct!(
+            self.x.to_inclusive(),
+            self.y.to_inclusive(),
+            self.z.to_inclusive()
+        )
+    }
+
+    /// Intersection of both cuboids, or `None` if they do not overlap.
+    fn intersect(&self, other: &Cuboid) -> Option<Cuboid> {
+        Some(Cuboid {
+            x: self.x.intersect(&other.x)?,
+            y: self.y.intersect(&other.y)?,
+            z: self.z.intersect(&other.z)?,
+        })
     }
 }
 
@@ -137,15 +168,40 @@ impl Interval {
         }
     }
 
-    /// As [`Range`](std::ops::Range) object
-    fn range(&self) -> Range<i64> {
-        self.start..self.end
+    /// Creates an integer interval spanning `radius` units from `center` on both sides, inclusive
+    fn centered(center: i64, radius: i64) -> Self {
+        Interval::new(center - radius, center + radius)
+    }
+
+    /// As [`RangeInclusive`] object with the same bounds as given to [`Interval::new`]
+    fn to_inclusive(&self) -> RangeInclusive<i64> {
+        self.start..=(self.end - 1)
     }
 
     /// Length of the interval
     fn len(&self) -> i64 {
         self.end - self.start
     }
+
+    /// Checks whether both intervals share at least one integer in common.
+    /// Due to half-open bounds, adjacent intervals such as `[0, 5)` and `[5, 10)` do not overlap.
+    fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// Intersection of both intervals, or `None` if they do not overlap.
+    fn intersect(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other).then(|| Interval {
+            start: i64::max(self.start, other.start),
+            end: i64::min(self.end, other.end),
+        })
+    }
+}
+
+impl From<RangeInclusive<i64>> for Interval {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Interval::new(*range.start(), *range.end())
+    }
 }
 
 impl Resident<Interval> for i64 {
@@ -180,11 +236,19 @@ impl FromStr for State {
 }
 
 /// Counts the number of on cubes within a small cuboid region
-fn on_cubes_in_small_cuboid(reboot_steps: &[RebootStep], region: &Cuboid) -> i64 {
-    let x_range = region.x.range();
-    let y_range = region.y.range();
-    let z_range = region.z.range();
-    iproduct!(x_range, y_range, z_range)
+fn on_cubes_in_small_cuboid(reboot_steps: &[RebootStep], region: &Cuboid) -> i128 {
+    // Clip reboot steps to the region and discard those which fall outside
+    let reboot_steps: Vec<_> = reboot_steps
+        .iter()
+        .filter_map(|s| {
+            Some(RebootStep {
+                cuboid: s.cuboid.intersect(region)?,
+                state: s.state.clone(),
+            })
+        })
+        .collect();
+    region
+        .points()
         .map(|p| {
             let state = reboot_steps
                 .iter()
@@ -200,7 +264,7 @@ fn on_cubes_in_small_cuboid(reboot_steps: &[RebootStep], region: &Cuboid) -> i64
 }
 
 /// Properly counts the number of on cubes
-fn on_cubes(reboot_steps: &[RebootStep]) -> i64 {
+fn on_cubes(reboot_steps: &[RebootStep]) -> i128 {
     let x_intervals =
         IntervalByCoords::intersect_from_intervals(reboot_steps.iter().map(|s| s.cuboid.x.clone()));
     let y_intervals =
@@ -261,3 +325,106 @@ impl Iterator for IntervalByCoords {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reboot_step() {
+        let step: RebootStep = " on x=1..2,y=3..4,z=-6..-5 ".parse().unwrap();
+        assert!(matches!(step.state, State::On));
+        assert_eq!(step.cuboid.z.to_inclusive(), -6..=-5);
+        let err = "on x=1..2,y=3..4,z=5..6 JUNK"
+            .parse::<RebootStep>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid line input: on x=1..2,y=3..4,z=5..6 JUNK"
+        );
+        assert!("JUNK off x=1..2,y=3..4,z=5..6"
+            .parse::<RebootStep>()
+            .is_err());
+    }
+
+    #[test]
+    fn interval_adjacent() {
+        let fst = Interval::new(0, 4);
+        let snd = Interval::new(5, 9);
+        assert!(!fst.overlaps(&snd));
+        assert!(!snd.overlaps(&fst));
+        assert!(fst.intersect(&snd).is_none());
+    }
+
+    #[test]
+    fn interval_nested() {
+        let outer = Interval::new(-10, 10);
+        let inner = Interval::new(-3, 2);
+        assert!(outer.overlaps(&inner));
+        assert_eq!(outer.intersect(&inner).unwrap().to_inclusive(), -3..=2);
+        assert_eq!(inner.intersect(&outer).unwrap().to_inclusive(), -3..=2);
+    }
+
+    #[test]
+    fn interval_partial_overlap() {
+        let fst = Interval::new(0, 5);
+        let snd = Interval::new(5, 9);
+        assert!(fst.overlaps(&snd));
+        let common = fst.intersect(&snd).unwrap();
+        assert_eq!(common.to_inclusive(), 5..=5);
+        assert_eq!(common.len(), 1);
+    }
+
+    #[test]
+    fn interval_inclusive_round_trip() {
+        for range in [-3..=7, 0..=0, 5..=6] {
+            let interval = Interval::from(range.clone());
+            assert_eq!(interval.to_inclusive(), range);
+            assert_eq!(interval.len(), range.count() as i64);
+        }
+        let interval = Interval::new(-50, 50);
+        let round_trip = Interval::from(interval.to_inclusive());
+        assert_eq!(
+            (round_trip.start, round_trip.end),
+            (interval.start, interval.end)
+        );
+    }
+
+    #[test]
+    fn small_cuboid_points() {
+        let cuboid = Cuboid {
+            x: Interval::new(10, 11),
+            y: Interval::new(-1, 0),
+            z: Interval::new(3, 4),
+        };
+        let points: Vec<_> = cuboid.points().collect();
+        assert_eq!(points.len(), 8);
+        assert_eq!(points.first(), Some(&(10, -1, 3)));
+        assert_eq!(points.last(), Some(&(11, 0, 4)));
+        assert!(points.iter().all(|p| p.within(&cuboid)));
+    }
+
+    #[test]
+    fn initialization_cube_volume() {
+        let cube = Cuboid::cube((0, 0, 0), 50);
+        assert_eq!(cube.x.to_inclusive(), -50..=50);
+        assert_eq!(cube.volume(), 101_i128.pow(3));
+        let shifted = Cuboid::cube((7, -3, 0), 1);
+        assert_eq!(shifted.y.to_inclusive(), -4..=-2);
+        assert_eq!(shifted.volume(), 27);
+    }
+
+    #[test]
+    fn huge_cuboid_volume() {
+        let bound = 1_000_000_000_000_i64;
+        let step: RebootStep = format!("on x={0}..{1},y={0}..{1},z={0}..{1}", -bound, bound)
+            .parse()
+            .unwrap();
+        let expected = 2_000_000_000_001_i128.pow(3);
+        assert!(expected > i64::MAX as i128);
+        assert_eq!(step.cuboid.volume(), expected);
+        assert_eq!(on_cubes(std::slice::from_ref(&step)), expected);
+        let off: RebootStep = "off x=0..9,y=0..9,z=0..9".parse().unwrap();
+        assert_eq!(on_cubes(&[step, off]), expected - 1000);
+    }
+}
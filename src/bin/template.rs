@@ -13,12 +13,16 @@ fn main() {
     let Input {} = Input::from_buffer(input_reader).expect("cannot parse input");
 
     // Part 1: TODO
-    let p1_answer = 0;
-    println!("Part 1 answer: {}", p1_answer);
+    if cli.part() != Some(2) {
+        let p1_answer = cli.time_part("Part 1", || 0);
+        println!("Part 1 answer: {}", p1_answer);
+    }
 
     // Part 2: TODO
-    let p2_answer = 0;
-    println!("Part 2 answer: {}", p2_answer);
+    if cli.part() != Some(1) {
+        let p2_answer = cli.time_part("Part 2", || 0);
+        println!("Part 2 answer: {}", p2_answer);
+    }
 }
 
 /// Program input data
@@ -0,0 +1,160 @@
+//! Implements a simple bit vector type backed by a vector of booleans.
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use anyhow::Context;
+
+/// Bit vector wrapper over a vector of boolean
+///
+/// # Implementation Note
+/// This approach wastes significant amount of memory,
+/// due to 8 bit being used to store a single boolean.
+/// - TODO: Use [`bitvec::BitVec`] from external crate instead
+///
+/// [`bitvec::BitVec`]: https://docs.rs/bitvec/latest/bitvec/vec/struct.BitVec.html
+#[derive(Debug, Clone)]
+pub struct BitVec(pub Vec<bool>);
+
+// NOTE: I cannot figure out how to get `impl From<_> for T` to work
+// for generic T: num::PrimInt + num::Unsigned. So using macros for now.
+macro_rules! impl_from_bitvec_for_int {
+    ($($t:ty),*) => {$(
+        impl From<&BitVec> for $t {
+            fn from(num: &BitVec) -> Self {
+                <$t>::from_str_radix(num.to_string().as_str(), 2).unwrap()
+            }
+        }
+    )*};
+    ($($t:ty,)*) => {
+        impl_from_bitvec_for_int!( $($t:ty),* )
+    };
+}
+impl_from_bitvec_for_int![usize, u8, u16, u32, u64, u128];
+
+impl BitVec {
+    /// Interprets the bit vector (in MSB-first order) as a signed integer
+    /// in two's complement representation whose width is the length of the bit vector.
+    /// An empty bit vector is interpreted as zero.
+    ///
+    /// # Panics
+    /// This method panics if the bit vector is longer than 64 bits.
+    pub fn to_signed_i64(&self) -> i64 {
+        assert!(
+            self.0.len() <= 64,
+            "bit vector too long for i64: {} bits",
+            self.0.len()
+        );
+        match self.0.split_first() {
+            None => 0,
+            Some((&sign, rest)) => {
+                let magnitude = rest.iter().fold(0_i64, |acc, &b| 2 * acc + b as i64);
+                if sign {
+                    magnitude + (i64::MIN >> (64 - self.0.len()))
+                } else {
+                    magnitude
+                }
+            }
+        }
+    }
+
+    /// Creates a bit vector (in MSB-first order) of the given `width`
+    /// from the two's complement representation of the signed integer.
+    /// Only the lowest `width` bits are kept if the value does not fit,
+    /// whereas widths beyond 64 bits are sign-extended.
+    pub fn from_i64(value: i64, width: usize) -> Self {
+        (0..width)
+            .rev()
+            .map(|i| match i {
+                0..=63 => (value >> i) & 1 == 1,
+                _ => value < 0,
+            })
+            .collect()
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s: String = self
+            .0
+            .iter()
+            .copied()
+            .map(|b| if b { '1' } else { '0' })
+            .collect();
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for BitVec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut inner = Vec::new();
+        for c in s.trim().chars() {
+            let d = c.to_digit(2).with_context(|| {
+                format!("invalid character in bit string: '{}'", c.escape_default())
+            })?;
+            inner.push(d != 0);
+        }
+        Ok(BitVec(inner))
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        BitVec(iter.into_iter().collect())
+    }
+}
+
+impl Deref for BitVec {
+    type Target = Vec<bool>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn unsigned_conversion() {
+        let bits: BitVec = "10110".parse().unwrap();
+        assert_eq!(u64::from(&bits), 22);
+        assert_eq!(bits.to_string(), "10110");
+    }
+
+    #[test_case("0111", 7)]
+    #[test_case("1000", -8)]
+    #[test_case("1111", -1)]
+    #[test_case("0000", 0)]
+    #[test_case("11111110", -2)]
+    #[test_case("10000001", -127)]
+    #[test_case("", 0)]
+    fn to_signed(s: &str, expected: i64) {
+        let bits: BitVec = s.parse().unwrap();
+        assert_eq!(bits.to_signed_i64(), expected);
+    }
+
+    #[test_case(-8, 4, "1000")]
+    #[test_case(-3, 4, "1101")]
+    #[test_case(5, 4, "0101")]
+    #[test_case(-1, 8, "11111111")]
+    #[test_case(-128, 8, "10000000")]
+    #[test_case(-100, 8, "10011100")]
+    fn signed_round_trip(value: i64, width: usize, expected: &str) {
+        let bits = BitVec::from_i64(value, width);
+        assert_eq!(bits.to_string(), expected);
+        assert_eq!(bits.to_signed_i64(), value);
+    }
+
+    #[test]
+    fn signed_extremes() {
+        assert_eq!(BitVec::from_i64(i64::MIN, 64).to_signed_i64(), i64::MIN);
+        assert_eq!(BitVec::from_i64(i64::MAX, 64).to_signed_i64(), i64::MAX);
+        assert_eq!(BitVec::from_i64(-1, 70).to_string(), "1".repeat(70));
+    }
+}
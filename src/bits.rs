@@ -0,0 +1,95 @@
+//! Conversions between integers, hexadecimal characters, and sequences of bits.
+//! Each bit is represented by `0` or `1` and sequences are arranged in MSB-first order.
+use anyhow::Context;
+use num::PrimInt;
+
+/// Converts a sequence of bits arranged in MSB-first order into an integer.
+pub fn bits_to_int<T>(bits: &[u8]) -> T
+where
+    T: PrimInt,
+{
+    bits.iter().fold(T::zero(), |acc, &bit| {
+        T::from(2).unwrap() * acc + T::from(bit).unwrap()
+    })
+}
+
+/// Converts the lowest `width` bits of an integer into a sequence of bits in MSB-first order.
+/// Bits beyond the size of the integer type are produced as `0`.
+pub fn int_to_bits<T>(value: T, width: usize) -> Vec<u8>
+where
+    T: PrimInt,
+{
+    let type_width = T::zero().count_zeros() as usize;
+    (0..width)
+        .rev()
+        .map(|pos| {
+            if pos < type_width && (value >> pos) & T::one() == T::one() {
+                1
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+/// Converts a hexadecimal character into an array of four bits in MSB-first order.
+pub fn hex_to_bits(c: char) -> anyhow::Result<[u8; 4]> {
+    let decimal = c
+        .to_digit(16)
+        .map(|d| d as u8)
+        .with_context(|| format!("not a hexadecimal character: '{}'", c.escape_default()))?;
+    Ok([
+        (0b1000 & decimal) / 0b1000,
+        (0b0100 & decimal) / 0b0100,
+        (0b0010 & decimal) / 0b0010,
+        (0b0001 & decimal),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case('0' => [0, 0, 0, 0])]
+    #[test_case('5' => [0, 1, 0, 1])]
+    #[test_case('A' => [1, 0, 1, 0])]
+    #[test_case('f' => [1, 1, 1, 1])]
+    fn hex_to_bits_valid(c: char) -> [u8; 4] {
+        hex_to_bits(c).unwrap()
+    }
+
+    #[test]
+    fn hex_to_bits_invalid() {
+        assert!(hex_to_bits('g').is_err());
+    }
+
+    #[test_case(&[] => 0)]
+    #[test_case(&[1, 1, 1, 1] => 15)]
+    #[test_case(&[1, 0, 1, 1, 0] => 22)]
+    fn bits_to_int_u64(bits: &[u8]) -> u64 {
+        bits_to_int(bits)
+    }
+
+    #[test_case(22, 5 => vec![1, 0, 1, 1, 0])]
+    #[test_case(22, 8 => vec![0, 0, 0, 1, 0, 1, 1, 0])]
+    #[test_case(22, 3 => vec![1, 1, 0])]
+    fn int_to_bits_u8(value: u8, width: usize) -> Vec<u8> {
+        int_to_bits(value, width)
+    }
+
+    #[test]
+    fn round_trip() {
+        for c in "0123456789abcdef".chars() {
+            let bits = hex_to_bits(c).unwrap();
+            let value: u32 = bits_to_int(&bits);
+            assert_eq!(value, c.to_digit(16).unwrap());
+            assert_eq!(int_to_bits(value, 4), bits.to_vec());
+        }
+        assert_eq!(
+            int_to_bits(u16::MAX, 20),
+            [vec![0; 4], vec![1; 16]].concat()
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use std::fmt::Debug;
 use std::iter::Peekable;
 
-use anyhow::bail;
+use anyhow::{anyhow, bail, ensure, Context};
 use arrayvec::ArrayVec;
 use itertools::Itertools;
 use thiserror::Error;
@@ -96,13 +96,62 @@ pub trait CollectArray: Iterator {
     /// Too few or too many items produced by the iterator will return in [`anyhow::Error`].
     /// If you wish to also recover partial data produced by the iterator,
     /// use [`collect_exact_recoverable`](CollectArray::collect_exact_recoverable) instead.
+    ///
+    /// Note that if the iterator reports an exact [`size_hint`](Iterator::size_hint)
+    /// which differs from `SIZE`, the error is returned right away without consuming any items.
     fn collect_exact<T, const SIZE: usize>(self) -> anyhow::Result<[T; SIZE]>
     where
         Self: Sized + Iterator<Item = T>,
     {
+        if let Some(err) = exact_size_mismatch(self.size_hint(), SIZE) {
+            return Err(err);
+        }
         generate_collect_method!(CHECKS_TOO_MANY:true, METHOD_RETURNS:anyhow, UNWRAPS_ITEM:false, self, SIZE)
     }
 
+    /// Collects exactly `R * C` items from the iterator into a nested constant-sized array
+    /// of `R` rows and `C` columns, filling the array in row-major order.
+    /// Too few or too many items produced by the iterator will return in [`anyhow::Error`]
+    /// with the same messages as [`collect_exact`](CollectArray::collect_exact) on `R * C` items.
+    fn collect_exact_grid<T, const R: usize, const C: usize>(self) -> anyhow::Result<[[T; C]; R]>
+    where
+        Self: Sized + Iterator<Item = T>,
+    {
+        let target = R * C;
+        if let Some(err) = exact_size_mismatch(self.size_hint(), target) {
+            return Err(err);
+        }
+        let mut it = self.peekable();
+        let mut rows: ArrayVec<[T; C], R> = ArrayVec::new();
+        for i in 0..R {
+            let mut row: ArrayVec<T, C> = ArrayVec::new();
+            for j in 0..C {
+                match it.next() {
+                    Some(item) => row.push(item),
+                    None => bail!(
+                        "too few items from the iterator (expected {} but found only {})",
+                        target,
+                        i * C + j
+                    ),
+                }
+            }
+            match row.into_inner() {
+                Ok(row) => rows.push(row),
+                Err(_) => unreachable!(),
+            }
+        }
+        if it.peek().is_some() {
+            bail!(
+                "too many items from the iterator (expected only {})",
+                target
+            );
+        }
+        match rows.into_inner() {
+            Ok(grid) => Ok(grid),
+            Err(_) => unreachable!(),
+        }
+    }
+
     /// Same as [`collect_exact`](CollectArray::collect_exact) but with error type [`CollectArrayError`]
     /// which contains partially processed data from the iterator.
     fn collect_exact_recoverable<T, const SIZE: usize>(
@@ -124,6 +173,49 @@ pub trait CollectArray: Iterator {
         generate_collect_method!(CHECKS_TOO_MANY:true, METHOD_RETURNS:anyhow, UNWRAPS_ITEM:true, self, SIZE)
     }
 
+    /// Writes all items from the iterator into the caller-owned array `out`, in order.
+    /// Too few or too many items produced by the iterator will return in [`anyhow::Error`].
+    ///
+    /// On error, `out` is left partially written: with too few items,
+    /// only the prefix of `out` corresponding to the produced items is overwritten
+    /// (the rest retains its previous values); with too many items,
+    /// all elements of `out` have been overwritten by the first `SIZE` items.
+    fn fill_exact<T, const SIZE: usize>(self, out: &mut [T; SIZE]) -> anyhow::Result<()>
+    where
+        Self: Sized + Iterator<Item = T>,
+    {
+        let mut it = self.peekable();
+        for (i, element) in out.iter_mut().enumerate() {
+            match it.next() {
+                Some(item) => *element = item,
+                None => bail!(
+                    "too few items from the iterator (expected {} but found only {})",
+                    SIZE,
+                    i
+                ),
+            }
+        }
+        if it.peek().is_some() {
+            bail!("too many items from the iterator (expected only {})", SIZE);
+        }
+        Ok(())
+    }
+
+    /// Same as [`try_collect_exact`](CollectArray::try_collect_exact) but the first error found
+    /// is additionally given the context of the index of the failing item.
+    fn try_collect_exact_indexed<T, E, const SIZE: usize>(self) -> anyhow::Result<[T; SIZE]>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        anyhow::Error: From<E>,
+    {
+        self.enumerate()
+            .map(|(idx, item)| {
+                item.map_err(anyhow::Error::from)
+                    .with_context(|| format!("item {} failed", idx))
+            })
+            .try_collect_exact::<T, anyhow::Error, SIZE>()
+    }
+
     /// Collects all items from the iterator into a constant-sized array.
     /// Too few items produced by the iterator will return in [`anyhow::Error`].
     /// If you wish to also recover partial data produced by the iterator,
@@ -158,13 +250,94 @@ pub trait CollectArray: Iterator {
     {
         generate_collect_method!(CHECKS_TOO_MANY:false, METHOD_RETURNS:anyhow, UNWRAPS_ITEM:true, self, SIZE)
     }
+
+    /// Same as [`collect_trunc`](CollectArray::collect_trunc) but also returns
+    /// the leftover items as a [`Peekable`] iterator so that the caller may resume working with it.
+    fn collect_trunc_peekable<T, const SIZE: usize>(
+        self,
+    ) -> anyhow::Result<([T; SIZE], Peekable<Self>)>
+    where
+        Self: Sized + Iterator<Item = T>,
+    {
+        let mut it = self.peekable();
+        let array = it.by_ref().collect_trunc()?;
+        Ok((array, it))
+    }
+
+    /// Collects the first few items from the iterator into a constant-sized array,
+    /// and then collects all leftover items into a [`Vec`].
+    /// Too few items produced by the iterator will return in [`anyhow::Error`].
+    ///
+    /// Note that this method exhausts the iterator. If you wish to leave extraneous items
+    /// in place, pass in a mutable reference (e.g. via [`Iterator::by_ref`]) to
+    /// [`collect_trunc`](CollectArray::collect_trunc) instead.
+    fn collect_split<T, const SIZE: usize>(self) -> anyhow::Result<([T; SIZE], Vec<T>)>
+    where
+        Self: Sized + Iterator<Item = T>,
+    {
+        let mut it = self;
+        let array = it.by_ref().collect_trunc()?;
+        Ok((array, it.collect()))
+    }
+
+    /// Collects at most `SIZE` items from the iterator into an [`ArrayVec`].
+    /// Unlike [`collect_trunc`](CollectArray::collect_trunc), too few items is not an error
+    /// and the resulting [`ArrayVec`] keeps exactly the items produced by the iterator.
+    ///
+    /// Note that extraneous items will not be consumed from the iterator
+    /// and thus the upstream caller may resume working with such iterator.
+    fn collect_min<T, const SIZE: usize>(self) -> ArrayVec<T, SIZE>
+    where
+        Self: Sized + Iterator<Item = T>,
+    {
+        self.take(SIZE).collect()
+    }
+
+    /// Partitions all items from the iterator into a constant number of buckets,
+    /// where the function `bucket` determines the index of the bucket for each item.
+    /// Items within each bucket retain their relative order from the iterator.
+    /// A bucket index out of range will return in [`anyhow::Error`].
+    fn partition_into<T, const SIZE: usize, F>(self, bucket: F) -> anyhow::Result<[Vec<T>; SIZE]>
+    where
+        Self: Sized + Iterator<Item = T>,
+        F: Fn(&T) -> usize,
+    {
+        let mut buckets = [(); SIZE].map(|_| Vec::new());
+        for item in self {
+            let index = bucket(&item);
+            ensure!(
+                index < SIZE,
+                "bucket index out of range (expected less than {} but found {})",
+                SIZE,
+                index
+            );
+            buckets[index].push(item);
+        }
+        Ok(buckets)
+    }
 }
 
 impl<I: ?Sized> CollectArray for I where I: Iterator {}
 
+/// Produces the same error as a full collection would if the `size_hint` of an iterator
+/// reports an exact number of items which differs from the `target` size.
+fn exact_size_mismatch(size_hint: (usize, Option<usize>), target: usize) -> Option<anyhow::Error> {
+    match size_hint {
+        (lower, Some(upper)) if lower == upper && lower < target => Some(anyhow!(
+            "too few items from the iterator (expected {} but found only {})",
+            target,
+            lower
+        )),
+        (lower, Some(upper)) if lower == upper && lower > target => Some(anyhow!(
+            "too many items from the iterator (expected only {})",
+            target
+        )),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use anyhow::Context;
     use paste::paste;
     use test_case::test_case;
 
@@ -200,6 +373,213 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test_case(0..10, [0, 1, 2, 3], vec![4, 5, 6, 7, 8, 9])]
+    #[test_case("xyz".chars(), ['x', 'y', 'z'], vec![])]
+    #[test_case(Vec::<usize>::new().into_iter(), [], vec![])]
+    fn split_ok<I, T, const SIZE: usize>(input_it: I, expected: [T; SIZE], leftover: Vec<T>)
+    where
+        I: Debug + Iterator<Item = T>,
+        T: Debug + PartialEq,
+    {
+        let result: ([_; SIZE], _) = input_it.collect_split().unwrap();
+        assert_eq!(result, (expected, leftover));
+    }
+
+    #[test]
+    fn exact_grid_ok() {
+        let grid: [[_; 3]; 2] = (0..6).collect_exact_grid().unwrap();
+        assert_eq!(grid, [[0, 1, 2], [3, 4, 5]]);
+        let grid: [[char; 0]; 0] = std::iter::empty().collect_exact_grid().unwrap();
+        assert_eq!(grid.len(), 0);
+    }
+
+    #[test]
+    fn exact_grid_wrong_count() {
+        assert_eq!(
+            (0..5)
+                .collect_exact_grid::<_, 2, 3>()
+                .unwrap_err()
+                .to_string(),
+            "too few items from the iterator (expected 6 but found only 5)"
+        );
+        assert_eq!(
+            (0..10)
+                .filter(|v| v % 2 == 0)
+                .collect_exact_grid::<_, 2, 2>()
+                .unwrap_err()
+                .to_string(),
+            "too many items from the iterator (expected only 4)"
+        );
+        assert_eq!(
+            (0..7)
+                .collect_exact_grid::<_, 2, 3>()
+                .unwrap_err()
+                .to_string(),
+            "too many items from the iterator (expected only 6)"
+        );
+    }
+
+    #[test]
+    fn fill_exact_ok() {
+        let mut out = [0; 4];
+        (1..5).fill_exact(&mut out).unwrap();
+        assert_eq!(out, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn fill_exact_too_few() {
+        let mut out = [0; 4];
+        assert_eq!(
+            (1..3).fill_exact(&mut out).unwrap_err().to_string(),
+            "too few items from the iterator (expected 4 but found only 2)"
+        );
+        assert_eq!(out, [1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn fill_exact_too_many() {
+        let mut out = [0; 4];
+        assert_eq!(
+            (1..10).fill_exact(&mut out).unwrap_err().to_string(),
+            "too many items from the iterator (expected only 4)"
+        );
+        assert_eq!(out, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn try_exact_indexed() {
+        let result: [_; 3] = "abc"
+            .chars()
+            .map(|v| v.to_digit(16).context("not a digit"))
+            .try_collect_exact_indexed()
+            .unwrap();
+        assert_eq!(result, [10, 11, 12]);
+        let err = "abxc"
+            .chars()
+            .map(|v| v.to_digit(16).context("not a digit"))
+            .try_collect_exact_indexed::<_, _, 4>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "item 2 failed");
+        assert_eq!(format!("{:#}", err), "item 2 failed: not a digit");
+    }
+
+    #[test]
+    fn trunc_peekable_resume() {
+        let (array, mut rest) = (0..10).collect_trunc_peekable::<_, 3>().unwrap();
+        assert_eq!(array, [0, 1, 2]);
+        assert_eq!(rest.peek(), Some(&3));
+        assert_eq!(rest.sum::<i32>(), 42);
+        assert_eq!(
+            (0..2)
+                .collect_trunc_peekable::<_, 3>()
+                .unwrap_err()
+                .to_string(),
+            "too few items from the iterator (expected 3 but found only 2)"
+        );
+    }
+
+    #[test]
+    fn split_too_few() {
+        assert_eq!(
+            (0..3).collect_split::<_, 4>().unwrap_err().to_string(),
+            "too few items from the iterator (expected 4 but found only 3)"
+        );
+    }
+
+    #[test_case(0..10, vec![0, 1, 2, 3])]
+    #[test_case("xyz".chars(), vec!['x', 'y', 'z'])]
+    #[test_case(0..2, vec![0, 1])]
+    #[test_case(Vec::<usize>::new().into_iter(), vec![])]
+    fn min_ok<I, T>(input_it: I, expected: Vec<T>)
+    where
+        I: Debug + Iterator<Item = T>,
+        T: Debug + PartialEq,
+    {
+        let result: ArrayVec<_, 4> = input_it.collect_min();
+        assert_eq!(result.into_iter().collect_vec(), expected);
+    }
+
+    #[test]
+    fn min_not_over_consumed() {
+        let mut it = 0..10;
+        let result: ArrayVec<_, 3> = it.by_ref().collect_min();
+        assert_eq!(result.as_slice(), [0, 1, 2]);
+        assert_eq!(it.collect_vec(), vec![3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    /// Iterator which reports a fixed size hint but panics if any item is requested
+    struct UntouchableIter(usize);
+
+    impl Iterator for UntouchableIter {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            panic!("the iterator should not be consumed")
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0, Some(self.0))
+        }
+    }
+
+    #[test]
+    fn exact_size_fast_path() {
+        assert_eq!(
+            UntouchableIter(2)
+                .collect_exact::<_, 3>()
+                .unwrap_err()
+                .to_string(),
+            "too few items from the iterator (expected 3 but found only 2)"
+        );
+        assert_eq!(
+            UntouchableIter(4)
+                .collect_exact::<_, 3>()
+                .unwrap_err()
+                .to_string(),
+            "too many items from the iterator (expected only 3)"
+        );
+    }
+
+    #[test]
+    fn exact_size_fast_path_not_consumed() {
+        let mut it = vec![1, 2, 3, 4].into_iter();
+        assert!(it.by_ref().collect_exact::<_, 3>().is_err());
+        assert_eq!(it.len(), 4);
+        let slice = [1, 2];
+        let mut it = slice.iter();
+        assert!(it.by_ref().collect_exact::<_, 3>().is_err());
+        assert_eq!(it.len(), 2);
+    }
+
+    #[test]
+    fn inexact_size_hint_slow_path() {
+        let mut it = (0..10).filter(|x| x % 3 == 0);
+        assert_eq!(
+            it.by_ref().collect_exact::<_, 3>().unwrap_err().to_string(),
+            "too many items from the iterator (expected only 3)"
+        );
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn partition_into_ok() {
+        let buckets: [Vec<_>; 3] = (0..10).partition_into(|v| v % 3).unwrap();
+        assert_eq!(buckets, [vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]);
+        let buckets: [Vec<i64>; 2] = std::iter::empty().partition_into(|_| 0).unwrap();
+        assert_eq!(buckets, [vec![], vec![]]);
+    }
+
+    #[test]
+    fn partition_into_out_of_range() {
+        assert_eq!(
+            (0..10)
+                .partition_into::<_, 3, _>(|v| v % 4)
+                .unwrap_err()
+                .to_string(),
+            "bucket index out of range (expected less than 3 but found 3)"
+        );
+    }
+
     macro_rules! test_collect_too_few {
         ($test_name:tt, $input:expr, $size:literal, $expected:expr) => {
             paste! {
@@ -0,0 +1,130 @@
+//! Implements a stack-based matcher for pairs of opening and closing delimiters.
+
+/// Possible outcomes for matching delimiters in a string
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DelimiterResult {
+    /// This variant indicates that, when scanning the string from left to right,
+    /// no mismatch between designated pairs of delimiters have been found.
+    /// However, the string may still be incomplete (i.e. hanging opening delimiters).
+    /// In such case, the string would contain the missing closing delimiters to complete it.
+    /// If the original string is already complete, the completion string would be empty.
+    Incomplete(String),
+    /// This variant indicates that, when scanning the string from left to right,
+    /// a mismatch between designated pairs of delimiters has been found.
+    /// In such case, it would keep track of the first invalid closing delimiter encountered.
+    Corrupted(char),
+}
+
+/// Detailed state of matching delimiters in a string produced by [`analyze_delimiters`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DelimiterAnalysis {
+    /// Opening delimiters which have not been closed yet, from the outermost to the innermost.
+    /// On corruption, this is the state just before the first invalid closing delimiter.
+    pub open_stack: Vec<char>,
+    /// The first invalid closing delimiter together with its character index in the string,
+    /// or `None` if the string is not corrupted
+    pub first_error: Option<(usize, char)>,
+}
+
+/// Analyzes opening and closing delimiters in the string `s`
+/// where `pairs` specifies all designated pairs of opening and closing delimiters.
+/// Characters which are neither opening nor closing delimiters are ignored.
+/// Scanning stops at the first invalid closing delimiter, if any.
+pub fn analyze_delimiters(s: &str, pairs: &[(char, char)]) -> DelimiterAnalysis {
+    let closing_of = |open: char| pairs.iter().find(|pair| pair.0 == open).map(|pair| pair.1);
+    let mut open_stack = Vec::with_capacity(16);
+    for (index, c) in s.chars().enumerate() {
+        if closing_of(c).is_some() {
+            open_stack.push(c);
+        } else if pairs.iter().any(|(_, close)| *close == c) {
+            if open_stack.last().copied().and_then(closing_of) == Some(c) {
+                open_stack.pop();
+            } else {
+                return DelimiterAnalysis {
+                    open_stack,
+                    first_error: Some((index, c)),
+                };
+            }
+        }
+    }
+    DelimiterAnalysis {
+        open_stack,
+        first_error: None,
+    }
+}
+
+/// Matches opening and closing delimiters in the string `s`
+/// where `pairs` specifies all designated pairs of opening and closing delimiters.
+/// Characters which are neither opening nor closing delimiters are ignored.
+/// This is a summary of the result from [`analyze_delimiters`].
+pub fn match_delimiters(s: &str, pairs: &[(char, char)]) -> DelimiterResult {
+    let analysis = analyze_delimiters(s, pairs);
+    match analysis.first_error {
+        Some((_, c)) => DelimiterResult::Corrupted(c),
+        None => {
+            let completion = analysis.open_stack.into_iter().rev().map(|open| {
+                pairs
+                    .iter()
+                    .find(|pair| pair.0 == open)
+                    .map(|pair| pair.1)
+                    .unwrap()
+            });
+            DelimiterResult::Incomplete(completion.collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+    #[test_case("[({(<(())[]>[[{[]{<()<>>", "}}]])})]" ; "incomplete 1")]
+    #[test_case("<{([{{}}[<[[[<>{}]]]>[]]", "])}>" ; "incomplete 2")]
+    #[test_case("()[]{}<>", "" ; "complete")]
+    #[test_case("", "" ; "empty")]
+    fn brackets_incomplete(s: &str, expected: &str) {
+        assert_eq!(
+            match_delimiters(s, &BRACKETS),
+            DelimiterResult::Incomplete(expected.to_string())
+        );
+    }
+
+    #[test_case("{([(<{}[<>[]}>{[]{[(<()>", '}' ; "corrupted 1")]
+    #[test_case("[[<[([]))<([[{}[[()]]]", ')' ; "corrupted 2")]
+    #[test_case("<{([([[(<>()){}]>(<<{{", '>' ; "corrupted 3")]
+    fn brackets_corrupted(s: &str, expected: char) {
+        assert_eq!(
+            match_delimiters(s, &BRACKETS),
+            DelimiterResult::Corrupted(expected)
+        );
+    }
+
+    #[test_case("(a[b)c", DelimiterResult::Incomplete("".to_string()) ; "ignore other chars")]
+    #[test_case("((x)", DelimiterResult::Incomplete(")".to_string()) ; "incomplete")]
+    #[test_case("(()))", DelimiterResult::Corrupted(')') ; "corrupted")]
+    #[test_case("{<(>}", DelimiterResult::Incomplete(")".to_string()) ; "ignore other brackets")]
+    fn parentheses_only(s: &str, expected: DelimiterResult) {
+        assert_eq!(match_delimiters(s, &[('(', ')')]), expected);
+    }
+
+    #[test]
+    fn analysis_on_corruption() {
+        let analysis = analyze_delimiters("{([(<{}[<>[]}>{[]{[(<()>", &BRACKETS);
+        assert_eq!(analysis.first_error, Some((12, '}')));
+        assert_eq!(analysis.open_stack, vec!['{', '(', '[', '(', '<', '[']);
+        let analysis = analyze_delimiters("a(b]", &BRACKETS);
+        assert_eq!(analysis.first_error, Some((3, ']')));
+        assert_eq!(analysis.open_stack, vec!['(']);
+    }
+
+    #[test]
+    fn analysis_on_incomplete() {
+        let analysis = analyze_delimiters("<{([{{}}[<[[[<>{}]]]>[]]", &BRACKETS);
+        assert_eq!(analysis.first_error, None);
+        assert_eq!(analysis.open_stack, vec!['<', '{', '(', '[']);
+    }
+}
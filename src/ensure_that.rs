@@ -3,6 +3,8 @@
 //! and returns itself wrapped inside [`Ok` result] if the predicate is satisfied.
 //!
 //! [`ensure_that`]: EnsureThat::ensure_that
+use std::fmt::Display;
+
 use anyhow::ensure;
 
 /// Trait extension that provides blanket implementation of the of method [`ensure_that`]
@@ -25,6 +27,37 @@ pub trait EnsureThat {
         );
         Ok(self)
     }
+
+    /// Same as [`ensure_that`] but with a custom error message `msg`
+    /// in place of the generic one when the predicate is not satisfied.
+    ///
+    /// [`ensure_that`]: EnsureThat::ensure_that
+    fn ensure_that_ctx(
+        self,
+        predicate: impl FnOnce(&Self) -> bool,
+        msg: impl Display,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        ensure!(predicate(&self), "{}", msg);
+        Ok(self)
+    }
+
+    /// Same as [`ensure_that`] but returns itself wrapped inside [`Some`]
+    /// if the predicate is satisfied, or [`None`] otherwise.
+    ///
+    /// [`ensure_that`]: EnsureThat::ensure_that
+    fn when(self, predicate: impl FnOnce(&Self) -> bool) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if predicate(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
 }
 
 impl<T: ?Sized> EnsureThat for T {}
@@ -56,4 +89,41 @@ mod tests {
             "the object failed to validate the provided predicate"
         );
     }
+
+    #[test]
+    fn with_custom_message() {
+        assert_eq!(
+            "Hello"
+                .ensure_that_ctx(|s| s.len() > 3, "too short")
+                .unwrap(),
+            "Hello"
+        );
+        assert_eq!(
+            2.ensure_that_ctx(|x| *x == 1, "expected player id 1")
+                .unwrap_err()
+                .to_string(),
+            "expected player id 1"
+        );
+        assert_eq!(
+            7.ensure_that_ctx(|x| *x < 5, format!("value {} exceeds {}", 7, 5))
+                .unwrap_err()
+                .to_string(),
+            "value 7 exceeds 5"
+        );
+    }
+
+    #[test]
+    fn when_on_integer() {
+        assert_eq!(3.when(|x| *x > 2), Some(3));
+        assert_eq!(20.when(|x| *x < 2), None);
+        let halve = |x: i64| Some(x.when(|x| x % 2 == 0)? / 2);
+        assert_eq!(halve(8), Some(4));
+        assert_eq!(halve(7), None);
+    }
+
+    #[test]
+    fn when_on_string() {
+        assert_eq!("Hello".when(|s| s.starts_with('H')), Some("Hello"));
+        assert_eq!(String::from("Hi").when(|s| s.len() > 3), None);
+    }
 }
@@ -0,0 +1,380 @@
+//! Provides geometric shapes on the two-dimensional cartesian plane.
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::{ensure, Context};
+use lazy_static::lazy_static;
+use num::rational::Ratio;
+use num::{Integer, PrimInt};
+use regex::Regex;
+
+use crate::hashing::{CountsFast, HashMap};
+use crate::parsing::QuickParse;
+
+/// Point in two-dimensional space
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
+pub struct Point2D<T> {
+    /// x-coordinate
+    pub x: T,
+    /// y-coordinate
+    pub y: T,
+}
+
+impl<T> Point2D<T> {
+    /// Creates a new point from its coordinates.
+    pub fn new(x: T, y: T) -> Self {
+        Point2D { x, y }
+    }
+}
+
+/// Line segment with end-point coordinates in two-dimensional space
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
+pub struct LineSegment<T> {
+    /// One end of the line segment
+    pub p: Point2D<T>,
+    /// Another end of the line segment
+    pub q: Point2D<T>,
+}
+
+impl<T> LineSegment<T> {
+    /// Creates a new line segment from both of its end points.
+    pub fn new(p: Point2D<T>, q: Point2D<T>) -> Self {
+        LineSegment { p, q }
+    }
+
+    /// An iterator which produces a sequence of integer coordinates
+    /// contained within the line segment, from point `p` to point `q`.
+    pub fn walk_integer_coords(&self) -> impl Iterator<Item = Point2D<T>> + '_
+    where
+        T: Integer + Copy,
+    {
+        let (dx, dy) = (self.q.x - self.p.x, self.q.y - self.p.y);
+        let steps = dx.gcd(&dy);
+
+        std::iter::successors(Some(self.p), move |&r| {
+            (self.q != r).then(|| Point2D::new(r.x + dx / steps, r.y + dy / steps))
+        })
+    }
+
+    /// Checks whether the line segment is axis-aligned.
+    pub fn is_axis_aligned(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.p.x == self.q.x || self.p.y == self.q.y
+    }
+
+    /// Slope of the line segment as an exact ratio of y-difference over x-difference.
+    /// Returns `None` for vertical line segments (including the degenerate single-point case).
+    pub fn slope(&self) -> Option<Ratio<T>>
+    where
+        T: Integer + Copy,
+    {
+        let (dx, dy) = (self.q.x - self.p.x, self.q.y - self.p.y);
+        (!dx.is_zero()).then(|| Ratio::new(dy, dx))
+    }
+}
+
+/// An iterator over integer coordinates walked by each line segment in order,
+/// optionally considering only the axis-aligned line segments.
+/// A point covered by multiple line segments is produced once per covering line segment.
+pub fn covered_points<T>(
+    segments: &[LineSegment<T>],
+    axis_aligned_only: bool,
+) -> impl Iterator<Item = Point2D<T>> + '_
+where
+    T: Integer + Copy,
+{
+    segments
+        .iter()
+        .filter(move |s| !axis_aligned_only || s.is_axis_aligned())
+        .flat_map(|s| s.walk_integer_coords())
+}
+
+/// Counts the number of line segments covering each integer point,
+/// optionally considering only the axis-aligned line segments.
+/// Points not covered by any line segment are absent from the map.
+pub fn coverage_map<T>(
+    segments: &[LineSegment<T>],
+    axis_aligned_only: bool,
+) -> HashMap<Point2D<T>, usize>
+where
+    T: Integer + Copy + Hash,
+{
+    covered_points(segments, axis_aligned_only).counts_fast()
+}
+
+impl<T> FromStr for LineSegment<T>
+where
+    T: FromStr,
+{
+    type Err = anyhow::Error;
+
+    /// Parses a line segment from string of the format `x1,y1 -> x2,y2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(
+                r"(?x)
+                    \s*(-?\d+)\s*,
+                    \s*(-?\d+)\s*->
+                    \s*(-?\d+)\s*,
+                    \s*(-?\d+)\s*"
+            )
+            .unwrap();
+        }
+        let captures = RE
+            .captures(s)
+            .with_context(|| format!("invalid line segment input: {}", s))?;
+        Ok(LineSegment {
+            p: Point2D::new(captures[1].quickparse()?, captures[2].quickparse()?),
+            q: Point2D::new(captures[3].quickparse()?, captures[4].quickparse()?),
+        })
+    }
+}
+
+/// Represents a bounded rectangular area
+#[derive(Debug, Clone, Copy)]
+pub struct Rect<T> {
+    /// Inclusive lower bound on (horizontal) x-value
+    pub horz_lower: T,
+    /// Inclusive upper bound on (horizontal) x-value
+    pub horz_upper: T,
+    /// Inclusive lower bound on (vertical) y-value
+    pub vert_lower: T,
+    /// Inclusive upper bound on (vertical) y-value
+    pub vert_upper: T,
+}
+
+impl<T> Rect<T> {
+    /// Constructs a new bounded rectangular area.
+    pub fn new(vert_upper: T, horz_upper: T, vert_lower: T, horz_lower: T) -> anyhow::Result<Self>
+    where
+        T: PrimInt + Display,
+    {
+        ensure!(
+            horz_lower <= horz_upper,
+            "conflicting horizontal range: {} > {}",
+            horz_lower,
+            horz_upper,
+        );
+        ensure!(
+            vert_lower <= vert_upper,
+            "conflicting vertical range: {} > {}",
+            vert_lower,
+            vert_upper,
+        );
+        Ok(Rect {
+            horz_lower,
+            horz_upper,
+            vert_lower,
+            vert_upper,
+        })
+    }
+
+    /// Obtains the rectangle area as a pair of horizontal and vertical [`RangeInclusive`].
+    pub fn as_range_inclusive(&self) -> (RangeInclusive<T>, RangeInclusive<T>)
+    where
+        T: PrimInt,
+    {
+        let horz_range = RangeInclusive::new(self.horz_lower, self.horz_upper);
+        let vert_range = RangeInclusive::new(self.vert_lower, self.vert_upper);
+        (horz_range, vert_range)
+    }
+
+    /// Checks whether the rectangle area contains the given point.
+    pub fn contains(&self, x: T, y: T) -> bool
+    where
+        T: PrimInt,
+    {
+        self.horz_lower <= x && x <= self.horz_upper && self.vert_lower <= y && y <= self.vert_upper
+    }
+
+    /// Checks whether the rectangle area entirely contains the `other` rectangle area.
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool
+    where
+        T: PrimInt,
+    {
+        self.contains(other.horz_lower, other.vert_lower)
+            && self.contains(other.horz_upper, other.vert_upper)
+    }
+
+    /// Counts the number of integer points within the rectangle area (inclusive on all bounds).
+    pub fn area(&self) -> T
+    where
+        T: PrimInt,
+    {
+        let width = self.horz_upper - self.horz_lower + T::one();
+        let height = self.vert_upper - self.vert_lower + T::one();
+        width * height
+    }
+
+    /// Intersection of both rectangle areas, or `None` if they do not overlap.
+    pub fn intersect(&self, other: &Rect<T>) -> Option<Rect<T>>
+    where
+        T: PrimInt,
+    {
+        let horz_lower = T::max(self.horz_lower, other.horz_lower);
+        let horz_upper = T::min(self.horz_upper, other.horz_upper);
+        let vert_lower = T::max(self.vert_lower, other.vert_lower);
+        let vert_upper = T::min(self.vert_upper, other.vert_upper);
+        (horz_lower <= horz_upper && vert_lower <= vert_upper).then(|| Rect {
+            horz_lower,
+            horz_upper,
+            vert_lower,
+            vert_upper,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::itertools_ext::ItertoolsExt;
+
+    use super::*;
+
+    static VENT_SAMPLE: [&str; 10] = [
+        "0,9 -> 5,9",
+        "8,0 -> 0,8",
+        "9,4 -> 3,4",
+        "2,2 -> 2,1",
+        "7,0 -> 7,4",
+        "6,4 -> 2,0",
+        "0,9 -> 2,9",
+        "3,4 -> 1,4",
+        "0,0 -> 8,8",
+        "5,5 -> 8,2",
+    ];
+
+    fn vent_sample() -> Vec<LineSegment<i64>> {
+        VENT_SAMPLE.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn vent_sample_hot_points() {
+        let segments = vent_sample();
+        assert_eq!(covered_points(&segments, true).count_at_least(2), 5);
+        assert_eq!(covered_points(&segments, false).count_at_least(2), 12);
+    }
+
+    #[test]
+    fn vent_sample_coverage_map() {
+        let segments = vent_sample();
+        let axis_aligned = coverage_map(&segments, true);
+        assert_eq!(axis_aligned[&Point2D::new(0, 9)], 2);
+        assert_eq!(axis_aligned[&Point2D::new(7, 4)], 2);
+        assert_eq!(axis_aligned[&Point2D::new(4, 4)], 1);
+        assert!(!axis_aligned.contains_key(&Point2D::new(5, 5)));
+        let all = coverage_map(&segments, false);
+        assert_eq!(all[&Point2D::new(4, 4)], 3);
+        assert_eq!(all[&Point2D::new(8, 0)], 1);
+        assert!(!all.contains_key(&Point2D::new(9, 9)));
+        assert_eq!(all.values().filter(|&&count| count >= 2).count(), 12);
+    }
+
+    #[test]
+    fn walk_integer_coords() {
+        let segment: LineSegment<i64> = "8,0 -> 5,3".parse().unwrap();
+        let coords: Vec<_> = segment.walk_integer_coords().collect();
+        let expected = [(8, 0), (7, 1), (6, 2), (5, 3)].map(|(x, y)| Point2D::new(x, y));
+        assert_eq!(coords, expected);
+        let segment: LineSegment<i64> = "2,4 -> 2,4".parse().unwrap();
+        assert_eq!(segment.walk_integer_coords().count(), 1);
+    }
+
+    #[test]
+    fn slope() {
+        let segment: LineSegment<i64> = "0,0 -> 4,2".parse().unwrap();
+        assert_eq!(segment.slope(), Some(Ratio::new(1, 2)));
+        let segment: LineSegment<i64> = "8,0 -> 0,8".parse().unwrap();
+        assert_eq!(segment.slope(), Some(Ratio::from_integer(-1)));
+        let segment: LineSegment<i64> = "2,2 -> 2,1".parse().unwrap();
+        assert_eq!(segment.slope(), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            "1,2 => 3,4"
+                .parse::<LineSegment<i64>>()
+                .unwrap_err()
+                .to_string(),
+            "invalid line segment input: 1,2 => 3,4"
+        );
+    }
+
+    fn rect(horz: (i64, i64), vert: (i64, i64)) -> Rect<i64> {
+        Rect::new(vert.1, horz.1, vert.0, horz.0).unwrap()
+    }
+
+    #[test]
+    fn area() {
+        assert_eq!(rect((0, 0), (0, 1)).area(), 2);
+        assert_eq!(rect((20, 30), (-10, -5)).area(), 66);
+        assert_eq!(rect((3, 7), (4, 4)).area(), 5);
+        assert_eq!(rect((5, 5), (5, 5)).area(), 1);
+    }
+
+    #[test]
+    fn new_conflicting_range() {
+        assert_eq!(
+            Rect::new(3, 5, 4, 6).unwrap_err().to_string(),
+            "conflicting horizontal range: 6 > 5"
+        );
+        assert_eq!(
+            Rect::new(3, 5, 4, 0).unwrap_err().to_string(),
+            "conflicting vertical range: 4 > 3"
+        );
+    }
+
+    #[test]
+    fn intersect_overlapping() {
+        let a = rect((0, 10), (0, 10));
+        let b = rect((5, 15), (-5, 3));
+        let c = a.intersect(&b).unwrap();
+        assert_eq!(c.as_range_inclusive(), (5..=10, 0..=3));
+        assert_eq!(
+            b.intersect(&a).unwrap().as_range_inclusive(),
+            (5..=10, 0..=3)
+        );
+    }
+
+    #[test]
+    fn intersect_single_row() {
+        let a = rect((0, 10), (0, 10));
+        let b = rect((-5, 5), (10, 20));
+        let c = a.intersect(&b).unwrap();
+        assert_eq!(c.as_range_inclusive(), (0..=5, 10..=10));
+        assert_eq!(
+            rect((0, 5), (10, 10)).as_range_inclusive(),
+            (0..=5, 10..=10)
+        );
+    }
+
+    #[test]
+    fn intersect_touching() {
+        let a = rect((0, 10), (0, 10));
+        let b = rect((10, 20), (10, 20));
+        let c = a.intersect(&b).unwrap();
+        assert_eq!(c.as_range_inclusive(), (10..=10, 10..=10));
+        assert_eq!(c.area(), 1);
+    }
+
+    #[test]
+    fn intersect_disjoint() {
+        let a = rect((0, 10), (0, 10));
+        assert!(a.intersect(&rect((11, 20), (0, 10))).is_none());
+        assert!(a.intersect(&rect((0, 10), (-5, -1))).is_none());
+        assert!(a.intersect(&rect((-9, -1), (20, 30))).is_none());
+    }
+
+    #[test]
+    fn contains_rect() {
+        let a = rect((0, 10), (0, 10));
+        assert!(a.contains_rect(&a));
+        assert!(a.contains_rect(&rect((2, 5), (3, 10))));
+        assert!(!a.contains_rect(&rect((2, 11), (3, 10))));
+        assert!(!rect((2, 5), (3, 10)).contains_rect(&a));
+    }
+}
@@ -32,12 +32,11 @@ where
             min_point.0 <= p.0 && p.0 <= max_point.0 && min_point.1 <= p.1 && p.1 <= max_point.1
         })
     }
-}
 
-impl OrthAdjacent<usize> {
-    /// Subsequence of grid points bounded by the rectangular `shape`.
-    pub fn within_shape(self, shape: GridPoint<usize>) -> impl Iterator<Item = GridPoint<usize>> {
-        self.filter(move |p| p.0 < shape.0 && p.1 < shape.1)
+    /// Subsequence of grid points bounded by the rectangular `shape`,
+    /// i.e. both coordinates are non-negative and strictly less than those of the `shape`.
+    pub fn within_shape(self, shape: GridPoint<T>) -> impl Iterator<Item = GridPoint<T>> {
+        self.filter(move |p| T::zero() <= p.0 && p.0 < shape.0 && T::zero() <= p.1 && p.1 < shape.1)
     }
 }
 
@@ -94,12 +93,11 @@ where
             min_point.0 <= p.0 && p.0 <= max_point.0 && min_point.1 <= p.1 && p.1 <= max_point.1
         })
     }
-}
 
-impl KingAdjacent<usize> {
-    /// Subsequence of grid points bounded by the rectangular `shape`.
-    pub fn within_shape(self, shape: GridPoint<usize>) -> impl Iterator<Item = GridPoint<usize>> {
-        self.filter(move |p| p.0 < shape.0 && p.1 < shape.1)
+    /// Subsequence of grid points bounded by the rectangular `shape`,
+    /// i.e. both coordinates are non-negative and strictly less than those of the `shape`.
+    pub fn within_shape(self, shape: GridPoint<T>) -> impl Iterator<Item = GridPoint<T>> {
+        self.filter(move |p| T::zero() <= p.0 && p.0 < shape.0 && T::zero() <= p.1 && p.1 < shape.1)
     }
 }
 
@@ -161,3 +159,50 @@ static KING_NEIGHBORS: [GridPoint<Step>; 8] = [
     (Step::Zero, Step::Sub1),
     (Step::Sub1, Step::Sub1),
 ];
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn orth_within_shape() {
+        let points = OrthAdjacent::new((0_usize, 2))
+            .within_shape((3, 3))
+            .collect_vec();
+        assert_eq!(points, vec![(1, 2), (0, 1)]);
+        let points = OrthAdjacent::new((0_i64, 2))
+            .within_shape((3, 3))
+            .collect_vec();
+        assert_eq!(points, vec![(1, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn orth_within_signed_region() {
+        let points = OrthAdjacent::new((-2_i64, 0))
+            .within_region((-2, -1), (1, 0))
+            .collect_vec();
+        assert_eq!(points, vec![(-1, 0), (-2, -1)]);
+    }
+
+    #[test]
+    fn king_within_signed_region() {
+        let points = KingAdjacent::new((0_i64, 0))
+            .within_region((-1, -1), (0, 1))
+            .collect_vec();
+        assert_eq!(points, vec![(-1, 0), (-1, 1), (0, 1), (0, -1), (-1, -1)]);
+        let points = KingAdjacent::new((-5_i64, 3))
+            .within_region((-5, 3), (5, 3))
+            .collect_vec();
+        assert_eq!(points, vec![(-4, 3)]);
+    }
+
+    #[test]
+    fn king_within_shape_excludes_negative() {
+        let points = KingAdjacent::new((0_i64, 0))
+            .within_shape((2, 2))
+            .collect_vec();
+        assert_eq!(points, vec![(0, 1), (1, 1), (1, 0)]);
+    }
+}
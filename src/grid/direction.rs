@@ -0,0 +1,117 @@
+use crate::grid::GridPoint;
+
+/// One of the eight compass directions on a grid,
+/// where the first coordinate grows southward (down the rows)
+/// and the second coordinate grows eastward (across the columns).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// All eight directions in clockwise order starting from the north.
+    const CLOCKWISE: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The four orthogonal directions in clockwise order starting from the north.
+    pub fn all() -> [Direction; 4] {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+    }
+
+    /// All eight directions (orthogonal and diagonal) in clockwise order starting from the north.
+    pub fn all_eight() -> [Direction; 8] {
+        Self::CLOCKWISE
+    }
+
+    /// Unit step in the grid point coordinates when moving one cell towards this direction.
+    pub fn offset(&self) -> GridPoint<i64> {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Direction obtained by turning 90 degrees counterclockwise.
+    pub fn turn_left(&self) -> Self {
+        self.rotate_clockwise(6)
+    }
+
+    /// Direction obtained by turning 90 degrees clockwise.
+    pub fn turn_right(&self) -> Self {
+        self.rotate_clockwise(2)
+    }
+
+    /// Direction pointing the opposite way.
+    pub fn opposite(&self) -> Self {
+        self.rotate_clockwise(4)
+    }
+
+    /// Rotates clockwise by the given number of eighth turns.
+    fn rotate_clockwise(&self, eighths: usize) -> Self {
+        Self::CLOCKWISE[(*self as usize + eighths) % 8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn four_left_turns_is_identity() {
+        for dir in Direction::all_eight() {
+            assert_eq!(dir.turn_left().turn_left().turn_left().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right().turn_right().turn_right(), dir);
+        }
+    }
+
+    #[test]
+    fn turns_are_inverse() {
+        for dir in Direction::all_eight() {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_left().turn_left(), dir.opposite());
+            assert_eq!(dir.opposite().opposite(), dir);
+        }
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::NorthEast.turn_left(), Direction::NorthWest);
+        for dir in Direction::all_eight() {
+            let (dr, dc) = dir.offset();
+            assert_eq!(dir.opposite().offset(), (-dr, -dc));
+            assert_eq!(dir.turn_right().offset(), (dc, -dr));
+        }
+        let sum = Direction::all().iter().fold((0, 0), |(r, c), dir| {
+            (r + dir.offset().0, c + dir.offset().1)
+        });
+        assert_eq!(sum, (0, 0));
+    }
+}
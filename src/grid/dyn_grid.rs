@@ -0,0 +1,196 @@
+//! Implements a two-dimensional grid whose shape is only known at run time.
+use std::ops::Index;
+
+use anyhow::{ensure, Context};
+
+use crate::grid::{bounds_check, GridIndices, GridPoint};
+
+/// Grid whose number of rows and columns are only known at run time,
+/// with items stored contiguously in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynGrid<T> {
+    /// Items of the grid in row-major order
+    data: Box<[T]>,
+    /// Number of rows in the grid
+    nrows: usize,
+    /// Number of columns in the grid
+    ncols: usize,
+}
+
+impl<T> DynGrid<T> {
+    /// Creates a new grid from a vector of rows.
+    /// All rows must have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, |row| row.len());
+        let mut data = Vec::with_capacity(nrows * ncols);
+        for (i, row) in rows.into_iter().enumerate() {
+            ensure!(
+                row.len() == ncols,
+                "inconsistent length of row {}: {} but expected {}",
+                i,
+                row.len(),
+                ncols
+            );
+            data.extend(row);
+        }
+        Ok(DynGrid {
+            data: data.into_boxed_slice(),
+            nrows,
+            ncols,
+        })
+    }
+
+    /// Creates a new grid from a multi-line string where each character is converted
+    /// into a cell by `map`. Leading and trailing whitespaces on each line are trimmed
+    /// and blank lines are skipped, just like [`parse_char_grid`] on a buffered reader.
+    ///
+    /// [`parse_char_grid`]: crate::parsing_grid::parse_char_grid
+    pub fn from_char_str<F>(s: &str, mut map: F) -> anyhow::Result<Self>
+    where
+        F: FnMut(char) -> anyhow::Result<T>,
+    {
+        let mut rows = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let row = line
+                .chars()
+                .map(&mut map)
+                .collect::<anyhow::Result<Vec<_>>>()
+                .with_context(|| format!("cannot parse grid cell on line {}", i + 1))?;
+            rows.push(row);
+        }
+        DynGrid::from_rows(rows)
+    }
+
+    /// Renders the grid as a multi-line string with one line per row (joined by newlines)
+    /// where each cell is converted into a single character by `map`.
+    /// This is the inverse of [`DynGrid::from_char_str`].
+    pub fn to_char_string<F>(&self, mut map: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut lines = Vec::with_capacity(self.nrows);
+        for i in 0..self.nrows {
+            lines.push(
+                (0..self.ncols)
+                    .map(|j| map(&self[(i, j)]))
+                    .collect::<String>(),
+            );
+        }
+        lines.join("\n")
+    }
+
+    /// Number of rows and columns of the grid
+    pub fn shape(&self) -> GridPoint<usize> {
+        (self.nrows, self.ncols)
+    }
+
+    /// Gets a reference to the item at the given grid point,
+    /// or `None` if the grid point is out of bounds.
+    pub fn get(&self, pos: GridPoint<usize>) -> Option<&T> {
+        let (i, j) = pos;
+        if bounds_check(pos, self.shape()) {
+            self.data.get(i * self.ncols + j)
+        } else {
+            None
+        }
+    }
+
+    /// Iterator over all grid points in row-major order
+    pub fn indices(&self) -> GridIndices {
+        GridIndices::row_major(self.shape())
+    }
+}
+
+impl<T> Index<GridPoint<usize>> for DynGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: GridPoint<usize>) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "grid index out of bounds: {:?} but the shape is {:?}",
+                index,
+                self.shape()
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn construct_and_index() {
+        let grid = DynGrid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.shape(), (2, 3));
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(0, 2)], 3);
+        assert_eq!(grid[(1, 1)], 5);
+        let items = grid.indices().map(|pos| grid[pos]).collect_vec();
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn construct_empty() {
+        let grid = DynGrid::<i64>::from_rows(Vec::new()).unwrap();
+        assert_eq!(grid.shape(), (0, 0));
+        assert_eq!(grid.indices().count(), 0);
+    }
+
+    #[test]
+    fn construct_ragged() {
+        assert_eq!(
+            DynGrid::from_rows(vec![vec![1, 2], vec![3]])
+                .unwrap_err()
+                .to_string(),
+            "inconsistent length of row 1: 1 but expected 2"
+        );
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let grid = DynGrid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.get((1, 2)), Some(&6));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid index out of bounds: (0, 3) but the shape is (2, 3)")]
+    fn index_out_of_bounds() {
+        let grid = DynGrid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let _ = grid[(0, 3)];
+    }
+
+    #[test]
+    fn char_string_round_trip() {
+        let s = "#..#\n.##.\n#...";
+        let grid = DynGrid::from_char_str(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => anyhow::bail!("invalid character: '{}'", c),
+        })
+        .unwrap();
+        assert_eq!(grid.shape(), (3, 4));
+        assert!(grid[(0, 3)] && grid[(1, 1)] && !grid[(2, 3)]);
+        assert_eq!(grid.to_char_string(|&b| if b { '#' } else { '.' }), s);
+    }
+
+    #[test]
+    fn from_char_str_invalid() {
+        let err = DynGrid::from_char_str("#.\n.x", |c| match c {
+            '#' | '.' => Ok(c == '#'),
+            _ => anyhow::bail!("invalid character: '{}'", c),
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse grid cell on line 2");
+        assert!(DynGrid::from_char_str("##\n#", |c| Ok(c == '#')).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::iter::StepBy;
+
 use num::Integer;
 
 use crate::grid::GridPoint;
@@ -45,6 +47,28 @@ impl GridIndices {
             order: DataOrder::ColumnMajor,
         }
     }
+
+    /// Yields every `step`-th grid index starting from the first one,
+    /// following the same ordering as the original iterator.
+    /// The returned iterator still reports its exact size.
+    ///
+    /// # Panics
+    /// This method panics if `step` is zero.
+    pub fn stride(self, step: usize) -> StepBy<Self> {
+        self.step_by(step)
+    }
+
+    /// Checks whether the grid point lies within the shape of the grid
+    /// (regardless of how many indices have already been consumed).
+    pub fn in_bounds(&self, pos: GridPoint<usize>) -> bool {
+        bounds_check(pos, (self.nrows, self.ncols))
+    }
+}
+
+/// Checks whether the grid point lies within the rectangular `shape`,
+/// i.e. both coordinates are strictly less than those of the `shape`.
+pub fn bounds_check(pos: GridPoint<usize>, shape: GridPoint<usize>) -> bool {
+    pos.0 < shape.0 && pos.1 < shape.1
 }
 
 impl Iterator for GridIndices {
@@ -73,3 +97,77 @@ impl Iterator for GridIndices {
 }
 
 impl ExactSizeIterator for GridIndices {}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn stride_row_major() {
+        let it = GridIndices::row_major((4, 4)).stride(2);
+        assert_eq!(it.len(), 8);
+        assert_eq!(
+            it.collect_vec(),
+            vec![
+                (0, 0),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 2),
+                (3, 0),
+                (3, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn stride_column_major() {
+        let it = GridIndices::column_major((4, 4)).stride(2);
+        assert_eq!(it.len(), 8);
+        assert_eq!(
+            it.collect_vec(),
+            vec![
+                (0, 0),
+                (2, 0),
+                (0, 1),
+                (2, 1),
+                (0, 2),
+                (2, 2),
+                (0, 3),
+                (2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn stride_uneven() {
+        let it = GridIndices::row_major((3, 3)).stride(4);
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.collect_vec(), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn bounds_at_edges_and_corners() {
+        let shape = (3, 4);
+        assert!(bounds_check((0, 0), shape));
+        assert!(bounds_check((2, 3), shape));
+        assert!(bounds_check((0, 3), shape));
+        assert!(bounds_check((2, 0), shape));
+        assert!(!bounds_check((3, 0), shape));
+        assert!(!bounds_check((0, 4), shape));
+        assert!(!bounds_check((3, 4), shape));
+        assert!(!bounds_check((0, 0), (0, 4)));
+    }
+
+    #[test]
+    fn in_bounds_ignores_consumption() {
+        let mut it = GridIndices::row_major((2, 2));
+        it.by_ref().for_each(drop);
+        assert!(it.in_bounds((1, 1)));
+        assert!(!it.in_bounds((1, 2)));
+        assert!(!it.in_bounds((2, 1)));
+    }
+}
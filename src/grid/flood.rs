@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+
+use crate::grid::{GridPoint, OrthAdjacent};
+use crate::hashing::HashSet;
+
+/// Uses breadth-first search to find the connected region of grid points reachable
+/// from the `start` grid point through orthogonally adjacent moves within the rectangular `shape`.
+/// A grid point is entered only if the predicate `can_enter` is satisfied;
+/// the `start` grid point itself is always included in the region.
+pub fn flood_fill<P>(
+    start: GridPoint<usize>,
+    shape: GridPoint<usize>,
+    can_enter: P,
+) -> HashSet<GridPoint<usize>>
+where
+    P: Fn(GridPoint<usize>) -> bool,
+{
+    let mut queue = VecDeque::from([start]);
+    let mut visited = HashSet::from_iter([start]);
+    while let Some(pos) = queue.pop_front() {
+        for other_pos in OrthAdjacent::new(pos).within_shape(shape) {
+            if !visited.contains(&other_pos) && can_enter(other_pos) {
+                queue.push_back(other_pos);
+                visited.insert(other_pos);
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static BASIN: [&str; 4] = ["#..#.", "#.##.", "##...", "....#"];
+
+    fn is_open(pos: GridPoint<usize>) -> bool {
+        BASIN[pos.0].as_bytes()[pos.1] == b'.'
+    }
+
+    #[test]
+    fn enclosed_region() {
+        let region = flood_fill((0, 1), (4, 5), is_open);
+        assert_eq!(region, HashSet::from_iter([(0, 1), (0, 2), (1, 1)]));
+    }
+
+    #[test]
+    fn winding_region() {
+        let region = flood_fill((0, 4), (4, 5), is_open);
+        assert_eq!(region.len(), 9);
+        assert!(region.contains(&(3, 0)));
+        assert!(!region.contains(&(0, 1)));
+    }
+
+    #[test]
+    fn start_always_included() {
+        let region = flood_fill((3, 4), (4, 5), |_| false);
+        assert_eq!(region, HashSet::from_iter([(3, 4)]));
+    }
+}
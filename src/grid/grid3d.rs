@@ -0,0 +1,61 @@
+//! Implements adjacency helpers for points in three-dimensional integer space.
+use itertools::iproduct;
+
+/// A tuple-triplet describing a point represented by integer coordinates in three-dimensional space.
+pub type Point3d = (i64, i64, i64);
+
+/// The six points which share a face with the center point `p`
+/// (i.e. differ by one along exactly one axis).
+pub fn neighbors6(p: Point3d) -> [Point3d; 6] {
+    let (x, y, z) = p;
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+/// The twenty-six points which share a face, an edge, or a corner with the center point `p`
+/// (i.e. differ by at most one along every axis, excluding the center itself).
+pub fn neighbors26(p: Point3d) -> [Point3d; 26] {
+    let (x, y, z) = p;
+    let mut result = [p; 26];
+    let offsets = iproduct!(-1..=1, -1..=1, -1..=1).filter(|&offset| offset != (0, 0, 0));
+    for (slot, (dx, dy, dz)) in result.iter_mut().zip(offsets) {
+        *slot = (x + dx, y + dy, z + dz);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn face_neighbors() {
+        let center = (3, -2, 0);
+        let neighbors = neighbors6(center);
+        assert_eq!(neighbors.iter().unique().count(), 6);
+        assert!(neighbors.iter().all(|&p| p != center));
+        assert!(neighbors
+            .iter()
+            .all(|&(x, y, z)| { (x - 3).abs() + (y + 2).abs() + z.abs() == 1 }));
+    }
+
+    #[test]
+    fn king_neighbors() {
+        let center = (3, -2, 0);
+        let neighbors = neighbors26(center);
+        assert_eq!(neighbors.iter().unique().count(), 26);
+        assert!(neighbors.iter().all(|&p| p != center));
+        assert!(neighbors6(center).iter().all(|p| neighbors.contains(p)));
+        assert!(neighbors
+            .iter()
+            .all(|&(x, y, z)| { (x - 3).abs() <= 1 && (y + 2).abs() <= 1 && z.abs() <= 1 }));
+    }
+}
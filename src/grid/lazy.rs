@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use crate::grid::GridPoint;
+
+/// Grid whose items are computed on-the-fly by the function `proxy_map`
+/// upon each lookup, rather than being stored in memory.
+#[derive(Debug, Clone)]
+pub struct LazyGrid<T, F>
+where
+    F: Fn(GridPoint<usize>) -> T,
+{
+    /// Number of rows and columns of the grid
+    shape: GridPoint<usize>,
+    /// Function computing the item at the given grid point
+    proxy_map: F,
+    _item: PhantomData<T>,
+}
+
+impl<T, F> LazyGrid<T, F>
+where
+    F: Fn(GridPoint<usize>) -> T,
+{
+    /// Creates a new lazily-computed grid of the given `shape`.
+    pub fn new(shape: GridPoint<usize>, proxy_map: F) -> Self {
+        LazyGrid {
+            shape,
+            proxy_map,
+            _item: PhantomData,
+        }
+    }
+
+    /// Number of rows and columns of the grid
+    pub fn shape(&self) -> GridPoint<usize> {
+        self.shape
+    }
+
+    /// Computes the item at the given grid point.
+    pub fn get(&self, pos: GridPoint<usize>) -> T {
+        (self.proxy_map)(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_major_numbering() {
+        let ncols = 7;
+        let grid = LazyGrid::new((5, ncols), |(i, j)| i * ncols + j);
+        assert_eq!(grid.shape(), (5, 7));
+        assert_eq!(grid.get((0, 0)), 0);
+        assert_eq!(grid.get((0, 6)), 6);
+        assert_eq!(grid.get((1, 0)), 7);
+        assert_eq!(grid.get((4, 6)), 34);
+    }
+}
@@ -1,11 +1,23 @@
 //! Custom implementation of grid data structure
 pub use crate::grid::adjacency::{KingAdjacent, OrthAdjacent};
-pub use crate::grid::enumerate::GridIndices;
-pub use crate::grid::nalgebra::MatrixExt;
+pub use crate::grid::direction::Direction;
+pub use crate::grid::dyn_grid::DynGrid;
+pub use crate::grid::enumerate::{bounds_check, GridIndices};
+pub use crate::grid::flood::flood_fill;
+pub use crate::grid::grid3d::{neighbors26, neighbors6, Point3d};
+pub use crate::grid::lazy::LazyGrid;
+pub use crate::grid::nalgebra::{render_grid, MatrixExt};
+pub use crate::grid::point::{add_points, manhattan, sub_points};
 
 /// A tuple-pair describing grid point represented by integer coordinates on cartesian plane.
 pub type GridPoint<T> = (T, T);
 
 mod adjacency;
+mod direction;
+mod dyn_grid;
 mod enumerate;
+mod flood;
+mod grid3d;
+mod lazy;
 mod nalgebra;
+mod point;
@@ -1,10 +1,40 @@
+use std::cmp::Reverse;
+use std::fmt::Display;
+
 use crate::grid::enumerate::GridIndices;
+use crate::grid::GridPoint;
 use nalgebra::{Dim, Matrix, RawStorage};
 
 /// Trait extension on [`nalgebra::Matrix`] struct type
 pub trait MatrixExt {
+    /// Type of each element of the matrix
+    type Element;
+
     /// An iterator producing column-major indices of the matrix
     fn indices(&self) -> GridIndices;
+
+    /// Finds the index of the first element satisfying the predicate `pred`
+    /// in column-major order, or `None` if there is no such element.
+    fn find_position<F>(&self, pred: F) -> Option<GridPoint<usize>>
+    where
+        F: Fn(&Self::Element) -> bool;
+
+    /// Same as [`find_position`](MatrixExt::find_position) but searches in row-major order.
+    fn find_position_row_major<F>(&self, pred: F) -> Option<GridPoint<usize>>
+    where
+        F: Fn(&Self::Element) -> bool;
+
+    /// Finds the index of the smallest element, or `None` if the matrix is empty.
+    /// Ties are broken by the first index in column-major order (same as [`indices`](MatrixExt::indices)).
+    fn argmin(&self) -> Option<GridPoint<usize>>
+    where
+        Self::Element: Ord;
+
+    /// Finds the index of the largest element, or `None` if the matrix is empty.
+    /// Ties are broken by the first index in column-major order (same as [`indices`](MatrixExt::indices)).
+    fn argmax(&self) -> Option<GridPoint<usize>>
+    where
+        Self::Element: Ord;
 }
 
 impl<T, R, C, S> MatrixExt for Matrix<T, R, C, S>
@@ -13,7 +43,108 @@ where
     C: Dim,
     S: RawStorage<T, R, C>,
 {
+    type Element = T;
+
     fn indices(&self) -> GridIndices {
         GridIndices::column_major(self.shape())
     }
+
+    fn find_position<F>(&self, pred: F) -> Option<GridPoint<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.indices().find(|&pos| pred(&self[pos]))
+    }
+
+    fn find_position_row_major<F>(&self, pred: F) -> Option<GridPoint<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        GridIndices::row_major(self.shape()).find(|&pos| pred(&self[pos]))
+    }
+
+    fn argmin(&self) -> Option<GridPoint<usize>>
+    where
+        T: Ord,
+    {
+        self.indices().min_by_key(|&pos| &self[pos])
+    }
+
+    fn argmax(&self) -> Option<GridPoint<usize>>
+    where
+        T: Ord,
+    {
+        // `min_by_key` keeps the first extreme element whereas `max_by_key` keeps the last
+        self.indices().min_by_key(|&pos| Reverse(&self[pos]))
+    }
+}
+
+/// Renders the matrix as a string with one line per row (joined by newlines)
+/// where the displayed cells in each row are concatenated without separators.
+/// Hence, each cell takes exactly one character for single-character displays such as digits.
+pub fn render_grid<T, R, C, S>(grid: &Matrix<T, R, C, S>) -> String
+where
+    T: Display,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    let (nrows, ncols) = grid.shape();
+    (0..nrows)
+        .map(|i| {
+            (0..ncols)
+                .map(|j| grid[(i, j)].to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{matrix, DMatrix};
+
+    use super::*;
+
+    #[test]
+    fn find_single_nine() {
+        let grid = matrix![1, 4, 7; 2, 9, 8; 3, 6, 5];
+        assert_eq!(grid.find_position(|&x| x == 9), Some((1, 1)));
+        assert_eq!(grid.find_position_row_major(|&x| x == 9), Some((1, 1)));
+        assert_eq!(grid.find_position(|&x| x > 9), None);
+    }
+
+    #[test]
+    fn find_position_order() {
+        let grid = matrix![1, 8; 8, 1];
+        assert_eq!(grid.find_position(|&x| x == 8), Some((1, 0)));
+        assert_eq!(grid.find_position_row_major(|&x| x == 8), Some((0, 1)));
+    }
+
+    #[test]
+    fn argmin_argmax_unique() {
+        let grid = matrix![5, 4, 7; 2, 9, 8; 3, 1, 6];
+        assert_eq!(grid.argmin(), Some((2, 1)));
+        assert_eq!(grid.argmax(), Some((1, 1)));
+    }
+
+    #[test]
+    fn argmin_argmax_tie() {
+        let grid = matrix![3, 1, 9; 1, 9, 3];
+        assert_eq!(grid.argmin(), Some((1, 0)));
+        assert_eq!(grid.argmax(), Some((1, 1)));
+        assert_eq!(DMatrix::<u8>::zeros(0, 0).argmin(), None);
+    }
+
+    #[test]
+    fn render_digits() {
+        let grid = DMatrix::from_row_slice(2, 3, &[1_u8, 2, 3, 4, 5, 6]);
+        assert_eq!(render_grid(&grid), "123\n456");
+    }
+
+    #[test]
+    fn render_chars() {
+        let grid = matrix!['#', '.', '#'; '.', '#', '.'];
+        assert_eq!(render_grid(&grid), "#.#\n.#.");
+    }
 }
@@ -0,0 +1,60 @@
+use std::ops::{Add, Sub};
+
+use num::Signed;
+
+use crate::grid::GridPoint;
+
+/// Adds two grid points coordinate-wise.
+pub fn add_points<T>(a: GridPoint<T>, b: GridPoint<T>) -> GridPoint<T>
+where
+    T: Add<Output = T>,
+{
+    (a.0 + b.0, a.1 + b.1)
+}
+
+/// Subtracts the grid point `b` from the grid point `a` coordinate-wise.
+pub fn sub_points<T>(a: GridPoint<T>, b: GridPoint<T>) -> GridPoint<T>
+where
+    T: Sub<Output = T>,
+{
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/// Manhattan distance (i.e. L1 distance) between two grid points.
+pub fn manhattan<T>(a: GridPoint<T>, b: GridPoint<T>) -> T
+where
+    T: Signed,
+{
+    let (dx, dy) = sub_points(a, b);
+    dx.abs() + dy.abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case((1, 2), (3, 4), (4, 6))]
+    #[test_case((-5, 2), (5, -2), (0, 0))]
+    #[test_case((0, 0), (-1, -1), (-1, -1))]
+    fn add(a: GridPoint<i64>, b: GridPoint<i64>, expected: GridPoint<i64>) {
+        assert_eq!(add_points(a, b), expected);
+        assert_eq!(add_points(b, a), expected);
+        assert_eq!(sub_points(expected, b), a);
+    }
+
+    #[test]
+    fn add_unsigned() {
+        assert_eq!(add_points::<usize>((3, 0), (1, 7)), (4, 7));
+        assert_eq!(sub_points::<usize>((3, 7), (1, 7)), (2, 0));
+    }
+
+    #[test_case((0, 0), (0, 0), 0)]
+    #[test_case((1, 2), (4, 6), 7)]
+    #[test_case((-3, 5), (2, -1), 11)]
+    fn manhattan_distance(a: GridPoint<i64>, b: GridPoint<i64>, expected: i64) {
+        assert_eq!(manhattan(a, b), expected);
+        assert_eq!(manhattan(b, a), expected);
+    }
+}
@@ -1,5 +1,6 @@
 //! Hashed collections with XXHash fast hashing algorithm
-use std::hash::BuildHasherDefault;
+use std::cmp::Reverse;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
 
 use twox_hash::XxHash64;
 
@@ -8,3 +9,244 @@ pub type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<XxHa
 
 /// HashSet with XXHash fast hashing algorithm
 pub type HashSet<T> = std::collections::HashSet<T, BuildHasherDefault<XxHash64>>;
+
+/// HashMap with XXHash fast hashing algorithm using a custom seed
+pub type SeededHashMap<K, V> = std::collections::HashMap<K, V, SeededXxHash64>;
+
+/// HashSet with XXHash fast hashing algorithm using a custom seed
+pub type SeededHashSet<T> = std::collections::HashSet<T, SeededXxHash64>;
+
+/// Builder of XXHash hashers all sharing the same fixed seed.
+/// Collections built upon the same seed are guaranteed to be hashed identically,
+/// and hence they iterate the same set of keys in the same order.
+///
+/// Note that [`HashMap`] and [`HashSet`] are equally deterministic
+/// since they always use the default seed of zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeededXxHash64(pub u64);
+
+impl BuildHasher for SeededXxHash64 {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        XxHash64::with_seed(self.0)
+    }
+}
+
+/// Creates an empty [`SeededHashMap`] whose hashers use the given `seed`.
+pub fn map_with_seed<K, V>(seed: u64) -> SeededHashMap<K, V> {
+    SeededHashMap::with_hasher(SeededXxHash64(seed))
+}
+
+/// Creates an empty [`SeededHashSet`] whose hashers use the given `seed`.
+pub fn set_with_seed<T>(seed: u64) -> SeededHashSet<T> {
+    SeededHashSet::with_hasher(SeededXxHash64(seed))
+}
+
+/// Trait extension for [`Iterator`] trait which adds [`counts_fast`] method.
+///
+/// [`counts_fast`]: CountsFast::counts_fast
+pub trait CountsFast: Iterator {
+    /// Counts the number of occurrences of each distinct item from the iterator.
+    /// This is the same as [`Itertools::counts`] but collects into [`HashMap`]
+    /// with XXHash fast hashing algorithm.
+    ///
+    /// [`Itertools::counts`]: itertools::Itertools::counts
+    fn counts_fast(self) -> HashMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: Hash + Eq,
+    {
+        let mut counts = HashMap::default();
+        for item in self {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl<I: ?Sized> CountsFast for I where I: Iterator {}
+
+/// Multiset which counts the number of occurrences of each distinct item,
+/// built upon [`HashMap`] with XXHash fast hashing algorithm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T>
+where
+    T: Hash + Eq,
+{
+    counts: HashMap<T, usize>,
+}
+
+impl<T> Counter<T>
+where
+    T: Hash + Eq,
+{
+    /// Creates an empty counter.
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::default(),
+        }
+    }
+
+    /// Adds a single occurrence of the item.
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Adds `n` occurrences of the item.
+    /// Adding zero occurrences leaves the counter untouched,
+    /// so that the item does not become a distinct item with zero count.
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n == 0 {
+            return;
+        }
+        *self.counts.entry(item).or_insert(0) += n;
+    }
+
+    /// Number of occurrences of the item, which is zero for items never added.
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Size of the multiset intersection with the `other` counter,
+    /// i.e. the sum of the smaller count of each item between both counters.
+    pub fn intersection_size(&self, other: &Counter<T>) -> usize {
+        let (smaller, larger) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        smaller
+            .counts
+            .iter()
+            .map(|(item, &n)| n.min(larger.get(item)))
+            .sum()
+    }
+
+    /// Number of distinct items in the counter
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Checks whether no items have been added to the counter.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// All distinct items and their counts ordered from the most common to the least common.
+    /// Items with equal counts appear in an unspecified order.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut entries: Vec<_> = self.counts.iter().map(|(item, &n)| (item, n)).collect();
+        entries.sort_by_key(|&(_, n)| Reverse(n));
+        entries
+    }
+
+    /// The smallest and the largest counts among all distinct items,
+    /// or `None` if the counter is empty.
+    pub fn min_max_counts(&self) -> Option<(usize, usize)> {
+        let min_count = self.counts.values().copied().min()?;
+        let max_count = self.counts.values().copied().max()?;
+        Some((min_count, max_count))
+    }
+}
+
+impl<T> Default for Counter<T>
+where
+    T: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Counter<T>
+where
+    T: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        iter.into_iter().for_each(|item| counter.add(item));
+        counter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_order() {
+        let build_map = || {
+            let mut map = map_with_seed(42);
+            map.extend((0..1000).map(|i| (i, i * i)));
+            map
+        };
+        assert!(build_map().keys().eq(build_map().keys()));
+
+        let build_set = || {
+            let mut set = set_with_seed(7);
+            set.extend((0..500).map(|i| format!("item{}", i)));
+            set
+        };
+        assert!(build_set().iter().eq(build_set().iter()));
+    }
+
+    #[test]
+    fn counts_fast_same_as_counts() {
+        let items = "the quick brown fox jumps over the lazy dog".chars();
+        let expected = items.clone().counts();
+        let result = items.counts_fast();
+        assert_eq!(result.len(), expected.len());
+        assert!(expected.iter().all(|(k, v)| result[k] == *v));
+        assert_eq!(result[&'o'], 4);
+        assert!(Vec::<i64>::new().into_iter().counts_fast().is_empty());
+    }
+
+    #[test]
+    fn counter_most_common() {
+        let mut counter: Counter<_> = "abracadabra".chars().collect();
+        counter.add_n('z', 3);
+        let most_common = counter.most_common();
+        assert_eq!(most_common[..2], [(&'a', 5), (&'z', 3)]);
+        assert_eq!(
+            most_common.iter().map(|&(_, n)| n).collect_vec(),
+            vec![5, 3, 2, 2, 1, 1]
+        );
+        assert_eq!(counter.get(&'b'), 2);
+        assert_eq!(counter.get(&'x'), 0);
+    }
+
+    #[test]
+    fn counter_min_max_counts() {
+        let mut counter = Counter::new();
+        assert_eq!(counter.min_max_counts(), None);
+        counter.add("x");
+        assert_eq!(counter.min_max_counts(), Some((1, 1)));
+        counter.add_n("y", 4);
+        counter.add("x");
+        assert_eq!(counter.min_max_counts(), Some((2, 4)));
+    }
+
+    #[test]
+    fn counter_add_zero() {
+        let mut counter = Counter::new();
+        counter.add_n('a', 0);
+        assert!(counter.is_empty());
+        assert_eq!(counter.min_max_counts(), None);
+        counter.add('b');
+        counter.add_n('c', 0);
+        assert_eq!(counter.len(), 1);
+        assert_eq!(counter.min_max_counts(), Some((1, 1)));
+    }
+
+    #[test]
+    fn counter_intersection_size() {
+        let fst: Counter<_> = "abracadabra".chars().collect();
+        let snd: Counter<_> = "cadaver".chars().collect();
+        assert_eq!(fst.intersection_size(&snd), 5);
+        assert_eq!(snd.intersection_size(&fst), 5);
+        assert_eq!(fst.intersection_size(&Counter::new()), 0);
+    }
+}
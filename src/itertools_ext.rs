@@ -0,0 +1,158 @@
+//! Implements a blanket trait extension for [`Iterator`] trait which adds
+//! a handful of counting methods complementary to those from [`itertools`].
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use num::Zero;
+
+use crate::hashing::CountsFast;
+
+/// Trait extension for [`Iterator`] trait which adds
+/// a handful of counting methods complementary to those from [`itertools`].
+pub trait ItertoolsExt: Iterator {
+    /// Counts the number of distinct items which appear at least `threshold` times in the iterator.
+    fn count_at_least(self, threshold: usize) -> usize
+    where
+        Self: Sized,
+        Self::Item: Hash + Eq,
+    {
+        let counts = self.counts_fast();
+        counts.values().filter(|&&v| v >= threshold).count()
+    }
+
+    /// Counts the number of times an item is strictly greater than its immediate predecessor.
+    fn count_increases(self) -> usize
+    where
+        Self: Sized,
+        Self::Item: PartialOrd,
+    {
+        let mut count = 0;
+        let mut prev = None;
+        for item in self {
+            if matches!(&prev, Some(p) if *p < item) {
+                count += 1;
+            }
+            prev = Some(item);
+        }
+        count
+    }
+
+    /// An iterator over the sums of every `window` consecutive items (i.e. a sliding window).
+    /// If `window` is zero or there are fewer than `window` items in total,
+    /// then the resulting iterator will be empty.
+    fn windowed_sums(self, window: usize) -> WindowedSums<Self>
+    where
+        Self: Sized,
+        Self::Item: Copy + Zero + Sub<Output = Self::Item>,
+    {
+        WindowedSums {
+            it: self,
+            window,
+            buffer: VecDeque::with_capacity(window),
+            sum: Self::Item::zero(),
+        }
+    }
+}
+
+impl<I: ?Sized> ItertoolsExt for I where I: Iterator {}
+
+/// An iterator over sliding window sums produced by [`ItertoolsExt::windowed_sums`]
+#[derive(Debug, Clone)]
+pub struct WindowedSums<I>
+where
+    I: Iterator,
+{
+    /// Underlying iterator of items
+    it: I,
+    /// Number of items to sum within each window
+    window: usize,
+    /// Items within the current window
+    buffer: VecDeque<I::Item>,
+    /// Running sum of items within the current window
+    sum: I::Item,
+}
+
+impl<I> Iterator for WindowedSums<I>
+where
+    I: Iterator,
+    I::Item: Copy + Add<Output = I::Item> + Sub<Output = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window == 0 {
+            return None;
+        }
+        if self.buffer.len() == self.window {
+            let oldest = self.buffer.pop_front().unwrap();
+            self.sum = self.sum - oldest;
+        }
+        while self.buffer.len() < self.window {
+            let item = self.it.next()?;
+            self.buffer.push_back(item);
+            self.sum = self.sum + item;
+        }
+        Some(self.sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(0, 4)]
+    #[test_case(1, 4)]
+    #[test_case(2, 2)]
+    #[test_case(3, 1)]
+    #[test_case(4, 0)]
+    fn count_at_least_points(threshold: usize, expected: usize) {
+        let points: Vec<(i64, i64)> = vec![(0, 0), (1, 2), (0, 0), (-3, 4), (1, 2), (0, 0), (5, 5)];
+        assert_eq!(points.into_iter().count_at_least(threshold), expected);
+    }
+
+    static SONAR_SAMPLE: [i64; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn sonar_sample() {
+        assert_eq!(SONAR_SAMPLE.into_iter().count_increases(), 7);
+        assert_eq!(
+            SONAR_SAMPLE.into_iter().windowed_sums(3).count_increases(),
+            5
+        );
+    }
+
+    #[test_case(0, vec![])]
+    #[test_case(1, vec![1, 2, 3, 4])]
+    #[test_case(2, vec![3, 5, 7])]
+    #[test_case(4, vec![10])]
+    #[test_case(5, vec![])]
+    fn windowed_sums(window: usize, expected: Vec<i64>) {
+        let result: Vec<_> = [1, 2, 3, 4].into_iter().windowed_sums(window).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn count_increases_ties() {
+        assert_eq!([3, 3, 2, 2, 5, 5].into_iter().count_increases(), 1);
+        assert_eq!(Vec::<i64>::new().into_iter().count_increases(), 0);
+    }
+
+    #[test_case(1, &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263] => 7)]
+    #[test_case(3, &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263] => 5)]
+    #[test_case(3, &[199, 200] => 0)]
+    fn windowed_sums_count_increases(window: usize, depths: &[i64]) -> usize {
+        depths
+            .iter()
+            .copied()
+            .windowed_sums(window)
+            .count_increases()
+    }
+
+    #[test]
+    fn count_at_least_empty() {
+        assert_eq!(Vec::<i64>::new().into_iter().count_at_least(0), 0);
+    }
+}
@@ -1,8 +1,20 @@
 pub mod argparser;
+pub mod automaton;
+pub mod bit_vec;
+pub mod bits;
 pub mod collect_array;
+pub mod delimiters;
 pub mod ensure_that;
+pub mod geometry;
 pub mod grid;
 pub mod hashing;
+pub mod itertools_ext;
+pub mod linalg;
+pub mod line_batches;
 pub mod parsing;
+pub mod parsing_grid;
+pub mod pathfinding;
+pub mod reflect;
+pub mod simulation;
 pub mod snailfish;
 pub mod vecmat;
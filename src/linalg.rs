@@ -0,0 +1,58 @@
+//! Implements linear algebra utilities on top of statically-sized [`nalgebra`] matrices.
+use nalgebra::SMatrix;
+
+/// Raises the square matrix `base` to the power of `exp`
+/// using the exponentiation by repeated squaring technique,
+/// which requires only `O(log exp)` matrix multiplications.
+/// The identity matrix is returned when `exp` is zero.
+///
+/// # Overflow
+/// Matrix multiplications are carried out with plain [`u64`] arithmetic,
+/// so any element overflowing [`u64`] will panic in debug builds
+/// and silently wrap around in release builds.
+pub fn matrix_pow<const N: usize>(base: SMatrix<u64, N, N>, exp: u64) -> SMatrix<u64, N, N> {
+    let mut result = SMatrix::identity();
+    let mut base = base;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result *= base;
+        }
+        exp /= 2;
+        if exp > 0 {
+            base = base * base;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::matrix;
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(0)]
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(3)]
+    #[test_case(7)]
+    #[test_case(10)]
+    #[test_case(16)]
+    fn matrix_pow_small(exp: u64) {
+        let base = matrix![
+            1, 1, 0;
+            1, 0, 2;
+            0, 3, 1;
+        ];
+        let expected = (0..exp).fold(SMatrix::<u64, 3, 3>::identity(), |acc, _| acc * base);
+        assert_eq!(matrix_pow(base, exp), expected);
+    }
+
+    #[test]
+    fn matrix_pow_fibonacci() {
+        let base = matrix![1, 1; 1, 0];
+        assert_eq!(matrix_pow(base, 90)[(0, 1)], 2880067194370816120);
+    }
+}
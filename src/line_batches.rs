@@ -0,0 +1,57 @@
+//! Implements an iterator over batches of lines separated by blank lines.
+use std::io::BufRead;
+
+use anyhow::Context;
+use itertools::Itertools;
+
+/// Groups lines from the buffered reader into batches separated by seemingly empty lines
+/// (which includes lines containing just whitespaces).
+/// Separator lines are not included in any batch,
+/// and consecutive separator lines (including leading and trailing ones) never produce empty batches.
+pub fn line_batches(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<Vec<String>>> {
+    reader.lines().batching(|it| {
+        let mut buffer = Vec::new();
+        for line in it {
+            match line.context("error while reading a line of string from input") {
+                Ok(s) if s.trim().is_empty() && buffer.is_empty() => continue,
+                Ok(s) if s.trim().is_empty() => return Some(Ok(buffer)),
+                Ok(s) => buffer.push(s),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if buffer.is_empty() {
+            None
+        } else {
+            Some(Ok(buffer))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_batches(input: &str) -> Vec<Vec<String>> {
+        line_batches(input.as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn multiple_sections() {
+        let batches = collect_batches("1,2,3\n\na b\nc d\n\ne\n");
+        assert_eq!(batches, vec![vec!["1,2,3"], vec!["a b", "c d"], vec!["e"]]);
+    }
+
+    #[test]
+    fn extra_blank_lines() {
+        let batches = collect_batches("\n  \nx\n\n\n\ny\nz\n\n  \n\n");
+        assert_eq!(batches, vec![vec!["x"], vec!["y", "z"]]);
+    }
+
+    #[test]
+    fn no_sections() {
+        assert!(collect_batches("").is_empty());
+        assert!(collect_batches("\n\n").is_empty());
+    }
+}
@@ -1,10 +1,20 @@
 //! Implements a trait extension for [`str`] primitive type which adds the method
-//! [`quickparse`] to simplify fallible parsing with [`anyhow::Result`] return type.
+//! [`quickparse`] to simplify fallible parsing with [`anyhow::Result`] return type,
+//! as well as [`parse_lines`] to parse each line from a buffered reader
+//! and [`parse_blocks`] to parse each blank-separated block of lines.
+//! Additionally, [`captures_strict`] matches a regular expression against an entire string.
 //!
 //! [`quickparse`]: QuickParse::quickparse
+use std::io::BufRead;
 use std::str::FromStr;
+use std::sync::Mutex;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, Context};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::hashing::HashMap;
+use crate::line_batches::line_batches;
 
 /// Trait extension for [`str`] primitive type which adds [`quickparse`] method.
 ///
@@ -33,6 +43,89 @@ impl QuickParse for str {
     }
 }
 
+/// Parses each line from the buffered reader into a target type that implements [`FromStr`].
+/// Leading and trailing whitespaces on each line are trimmed and blank lines are skipped.
+/// Parsing stops at the first error, whose context mentions the (one-based) line number.
+/// Each line is parsed with [`quickparse`], so any [`FromStr`] error type is accepted.
+///
+/// [`quickparse`]: QuickParse::quickparse
+pub fn parse_lines<F>(reader: impl BufRead) -> anyhow::Result<Vec<F>>
+where
+    F: FromStr,
+{
+    parse_lines_filtered(reader, str::is_empty)
+}
+
+/// Same as [`parse_lines`] but skips the (trimmed) lines for which the predicate `skip` holds,
+/// such as blank lines or comment lines, instead of only blank lines.
+pub fn parse_lines_filtered<F>(
+    reader: impl BufRead,
+    skip: impl Fn(&str) -> bool,
+) -> anyhow::Result<Vec<F>>
+where
+    F: FromStr,
+{
+    let mut items = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.context("cannot read a line of string")?;
+        let line = line.trim();
+        if skip(line) {
+            continue;
+        }
+        let item = line
+            .quickparse()
+            .with_context(|| format!("cannot parse line {}: {}", i + 1, line))?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Parses each block of lines from the buffered reader using the function `parse_block`,
+/// where blocks are separated by blank lines as grouped by [`line_batches`].
+/// Parsing stops at the first error, whose context mentions the (one-based) block number.
+pub fn parse_blocks<T, F>(reader: impl BufRead, parse_block: F) -> anyhow::Result<Vec<T>>
+where
+    F: Fn(Vec<String>) -> anyhow::Result<T>,
+{
+    let mut items = Vec::new();
+    for (i, batch) in line_batches(reader).enumerate() {
+        let item = parse_block(batch?).with_context(|| format!("cannot parse block {}", i + 1))?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Matches the regular expression `re` against the string `s` and returns the capture groups.
+/// Unlike [`Regex::captures`], the match must span the entire string,
+/// so that inputs with leading or trailing garbage around the match are rejected.
+///
+/// # Implementation Note
+/// The match is carried out by an anchored copy of `re` (i.e. `\A(?:re)\z`),
+/// since checking the span of the leftmost-first match of `re` itself would reject
+/// strings which only a longer alternative spans entirely (such as `a|ab` against `ab`).
+/// Anchored copies are compiled once per pattern and cached.
+pub fn captures_strict<'t>(re: &Regex, s: &'t str) -> anyhow::Result<Captures<'t>> {
+    lazy_static! {
+        static ref ANCHORED: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::default());
+    }
+    let anchored = {
+        let mut cache = ANCHORED.lock().unwrap();
+        match cache.get(re.as_str()) {
+            Some(anchored) => anchored.clone(),
+            None => {
+                let anchored = Regex::new(format!(r"\A(?:{})\z", re.as_str()).as_str())?;
+                cache.insert(re.as_str().to_string(), anchored.clone());
+                anchored
+            }
+        }
+    };
+    match anchored.captures(s) {
+        Some(captures) => Ok(captures),
+        None if re.is_match(s) => bail!("unexpected input outside the matched pattern: {}", s),
+        None => bail!("input does not match the pattern: {}", s),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,4 +149,99 @@ mod tests {
             "cannot parse token for type u32: -3"
         );
     }
+
+    #[test]
+    fn parse_lines_ok() {
+        let items: Vec<i64> = parse_lines(" 3\n-1\n\n42 \n".as_bytes()).unwrap();
+        assert_eq!(items, vec![3, -1, 42]);
+    }
+
+    #[test]
+    fn parse_lines_err() {
+        let err = parse_lines::<u32>("3\n\n5\nx7\n9\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse line 4: x7");
+        assert_eq!(
+            err.root_cause().to_string(),
+            "cannot parse token for type u32: x7"
+        );
+    }
+
+    #[test]
+    fn parse_lines_filtered_comments() {
+        let input = "# header\n3\n\n  # indented comment\n-1\n42 # trailing\n";
+        let skip = |line: &str| line.is_empty() || line.starts_with('#');
+        let err = parse_lines_filtered::<i64>(input.as_bytes(), skip).unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse line 6: 42 # trailing");
+        let items: Vec<i64> =
+            parse_lines_filtered(input.replace(" # trailing", "").as_bytes(), skip).unwrap();
+        assert_eq!(items, vec![3, -1, 42]);
+    }
+
+    /// Parses a block of lines as the sum of integers on all lines
+    fn sum_block(lines: Vec<String>) -> anyhow::Result<i64> {
+        let mut total = 0;
+        for line in lines {
+            total += line.trim().parse::<i64>()?;
+        }
+        Ok(total)
+    }
+
+    #[test]
+    fn parse_blocks_ok() {
+        let sums = parse_blocks("1\n2\n\n\n3\n-4\n5\n".as_bytes(), sum_block).unwrap();
+        assert_eq!(sums, vec![3, 4]);
+    }
+
+    #[test]
+    fn parse_blocks_err() {
+        let err = parse_blocks("1\n2\n\n3\nfour\n".as_bytes(), sum_block).unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse block 2");
+        assert_eq!(
+            err.root_cause().to_string(),
+            "invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn captures_strict_ok() {
+        let re = Regex::new(r"\s*x=(-?\d+)\.\.(-?\d+)\s*").unwrap();
+        let captures = captures_strict(&re, " x=-3..7 ").unwrap();
+        assert_eq!((&captures[1], &captures[2]), ("-3", "7"));
+    }
+
+    #[test]
+    fn captures_strict_junk() {
+        let re = Regex::new(r"\s*x=(-?\d+)\.\.(-?\d+)\s*").unwrap();
+        assert!(re.captures("x=1..2 JUNK").is_some());
+        assert_eq!(
+            captures_strict(&re, "x=1..2 JUNK").unwrap_err().to_string(),
+            "unexpected input outside the matched pattern: x=1..2 JUNK"
+        );
+        assert_eq!(
+            captures_strict(&re, "JUNK x=1..2").unwrap_err().to_string(),
+            "unexpected input outside the matched pattern: JUNK x=1..2"
+        );
+        assert_eq!(
+            captures_strict(&re, "y=1..2").unwrap_err().to_string(),
+            "input does not match the pattern: y=1..2"
+        );
+    }
+
+    #[test]
+    fn captures_strict_longer_alternative() {
+        let re = Regex::new(r"(a|ab)").unwrap();
+        assert_eq!(&re.captures("ab").unwrap()[0], "a");
+        assert_eq!(&captures_strict(&re, "ab").unwrap()[1], "ab");
+        assert_eq!(&captures_strict(&re, "a").unwrap()[1], "a");
+        assert!(captures_strict(&re, "abb").is_err());
+    }
+
+    #[test]
+    fn captures_strict_flags() {
+        let re = Regex::new(r"(?i)player\s+(\d+)").unwrap();
+        assert_eq!(&captures_strict(&re, "PLAYER 2").unwrap()[1], "2");
+        let re = Regex::new(r"(?x) (on|off) \s+ (\d+)").unwrap();
+        let captures = captures_strict(&re, "off 12").unwrap();
+        assert_eq!((&captures[1], &captures[2]), ("off", "12"));
+    }
 }
@@ -0,0 +1,158 @@
+//! Implements parsing utilities for two-dimensional grids of characters.
+use std::io::BufRead;
+
+use anyhow::{ensure, Context};
+use nalgebra::{DMatrix, Scalar};
+
+use crate::grid::DynGrid;
+
+/// Parses lines of single decimal digits from the buffered reader into a matrix,
+/// where each line becomes a row of the matrix.
+/// Leading and trailing whitespaces on each line are trimmed and blank lines are skipped.
+/// Non-digit characters and rows of inconsistent lengths are rejected with an error.
+pub fn parse_digit_grid<T>(reader: impl BufRead) -> anyhow::Result<DMatrix<T>>
+where
+    T: Scalar + From<u8>,
+{
+    let mut elements = Vec::new();
+    let mut ncols = None;
+    let mut nrows = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.context("cannot read a line of string")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut row_len = 0;
+        for c in line.chars() {
+            let d = c.to_digit(10).with_context(|| {
+                format!(
+                    "invalid character in decimal string on line {}: '{}'",
+                    i + 1,
+                    c.escape_default()
+                )
+            })? as u8;
+            elements.push(T::from(d));
+            row_len += 1;
+        }
+        let expected_len = *ncols.get_or_insert(row_len);
+        ensure!(
+            row_len == expected_len,
+            "inconsistent row length on line {}: {} but expected {}",
+            i + 1,
+            row_len,
+            expected_len
+        );
+        nrows += 1;
+    }
+    Ok(DMatrix::from_row_slice(
+        nrows,
+        ncols.unwrap_or(0),
+        elements.as_slice(),
+    ))
+}
+
+/// Parses lines of characters from the buffered reader into a grid,
+/// where each line becomes a row and each character is converted into a cell by `map`.
+/// Leading and trailing whitespaces on each line are trimmed and blank lines are skipped.
+/// Errors from `map` and rows of inconsistent lengths are rejected with an error.
+pub fn parse_char_grid<T, F>(reader: impl BufRead, mut map: F) -> anyhow::Result<DynGrid<T>>
+where
+    F: FnMut(char) -> anyhow::Result<T>,
+{
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.context("cannot read a line of string")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let row = line
+            .chars()
+            .map(&mut map)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .with_context(|| format!("cannot parse grid cell on line {}", i + 1))?;
+        if let Some(first_row) = rows.first() {
+            ensure!(
+                row.len() == first_row.len(),
+                "inconsistent row length on line {}: {} but expected {}",
+                i + 1,
+                row.len(),
+                first_row.len()
+            );
+        }
+        rows.push(row);
+    }
+    DynGrid::from_rows(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ok() {
+        let grid: DMatrix<i64> = parse_digit_grid("  219\n398 \n\n".as_bytes()).unwrap();
+        assert_eq!(grid, DMatrix::from_row_slice(2, 3, &[2, 1, 9, 3, 9, 8]));
+    }
+
+    #[test]
+    fn parse_empty() {
+        let grid: DMatrix<u8> = parse_digit_grid("".as_bytes()).unwrap();
+        assert_eq!(grid.shape(), (0, 0));
+    }
+
+    #[test]
+    fn parse_invalid_char() {
+        assert_eq!(
+            parse_digit_grid::<u8>("12\n3x\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "invalid character in decimal string on line 2: 'x'"
+        );
+    }
+
+    #[test]
+    fn parse_ragged_rows() {
+        assert_eq!(
+            parse_digit_grid::<u8>("123\n45\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "inconsistent row length on line 2: 2 but expected 3"
+        );
+    }
+
+    fn parse_pixel(c: char) -> anyhow::Result<bool> {
+        match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => anyhow::bail!("invalid pixel character: '{}'", c.escape_default()),
+        }
+    }
+
+    #[test]
+    fn parse_char_ok() {
+        let grid = parse_char_grid(" #..\n.#.\n\n".as_bytes(), parse_pixel).unwrap();
+        assert_eq!(
+            grid,
+            DynGrid::from_rows(vec![vec![true, false, false], vec![false, true, false]]).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_char_invalid() {
+        let err = parse_char_grid("#.\n.x\n".as_bytes(), parse_pixel).unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse grid cell on line 2");
+        assert_eq!(err.root_cause().to_string(), "invalid pixel character: 'x'");
+    }
+
+    #[test]
+    fn parse_char_ragged_rows() {
+        assert_eq!(
+            parse_char_grid("##\n#\n".as_bytes(), parse_pixel)
+                .unwrap_err()
+                .to_string(),
+            "inconsistent row length on line 2: 1 but expected 2"
+        );
+    }
+}
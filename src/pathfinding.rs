@@ -0,0 +1,98 @@
+//! Implements generic graph traversal utilities.
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::hashing::HashSet;
+
+/// Uses breadth-first search to traverse the graph from all `starts` nodes simultaneously,
+/// producing each reachable node exactly once alongside its depth
+/// (i.e. the number of edges on the shortest path from the nearest starting node).
+/// Nodes are produced in non-decreasing order of depth,
+/// and the function `neighbors` is called on each node to discover its adjacent nodes.
+pub fn bfs_layers<N, FN, IN>(starts: Vec<N>, neighbors: FN) -> impl Iterator<Item = (usize, N)>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::default();
+    let queue = starts
+        .into_iter()
+        .filter(|node| visited.insert(node.clone()))
+        .map(|node| (0, node))
+        .collect();
+    BfsLayers {
+        queue,
+        visited,
+        neighbors,
+    }
+}
+
+/// Iterator of nodes produced by [`bfs_layers`]
+struct BfsLayers<N, FN> {
+    queue: VecDeque<(usize, N)>,
+    visited: HashSet<N>,
+    neighbors: FN,
+}
+
+impl<N, FN, IN> Iterator for BfsLayers<N, FN>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    type Item = (usize, N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.queue.pop_front()?;
+        for other_node in (self.neighbors)(&node) {
+            if self.visited.insert(other_node.clone()) {
+                self.queue.push_back((depth + 1, other_node));
+            }
+        }
+        Some((depth, node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    /// Adjacency lists of a small undirected graph with two components:
+    /// a square 0-1-2-3-0 with a tail 2-4-5, and an isolated edge 6-7.
+    static EDGES: [&[usize]; 8] = [
+        &[1, 3],
+        &[0, 2],
+        &[1, 3, 4],
+        &[0, 2],
+        &[2, 5],
+        &[4],
+        &[7],
+        &[6],
+    ];
+
+    #[test]
+    fn depths_from_single_start() {
+        let layers = bfs_layers(vec![0], |&n| EDGES[n].iter().copied()).collect_vec();
+        assert_eq!(layers.len(), 6);
+        let depths = layers.into_iter().sorted_by_key(|&(_, n)| n).collect_vec();
+        assert_eq!(depths, vec![(0, 0), (1, 1), (2, 2), (1, 3), (3, 4), (4, 5)]);
+    }
+
+    #[test]
+    fn depths_non_decreasing() {
+        let layers = bfs_layers(vec![5], |&n| EDGES[n].iter().copied()).collect_vec();
+        assert!(layers.iter().tuple_windows().all(|(a, b)| a.0 <= b.0));
+        assert_eq!(layers.last(), Some(&(4, 0)));
+    }
+
+    #[test]
+    fn multiple_starts() {
+        let layers = bfs_layers(vec![0, 5, 0], |&n| EDGES[n].iter().copied())
+            .sorted_by_key(|&(_, n)| n)
+            .collect_vec();
+        assert_eq!(layers, vec![(0, 0), (1, 1), (2, 2), (1, 3), (1, 4), (0, 5)]);
+    }
+}
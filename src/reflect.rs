@@ -0,0 +1,36 @@
+//! Implements reflection of coordinates about an axis, such as folding a sheet of paper.
+
+/// Reflects a coordinate `value` lying beyond the `axis` back onto the near side of it,
+/// while values on the near side (including the axis itself) remain in place.
+/// In other words, this computes `min(value, 2 * axis - value)`.
+///
+/// Values farther than `2 * axis` would be reflected into negative coordinates,
+/// so they are clamped to zero instead.
+/// The distance beyond the axis is subtracted from the axis rather than computing `2 * axis`,
+/// so that axes in the upper half of the `u64` range do not overflow.
+pub fn reflect(value: u64, axis: u64) -> u64 {
+    match value.checked_sub(axis) {
+        Some(beyond) => axis.saturating_sub(beyond),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(3, 7 => 3; "near side")]
+    #[test_case(0, 7 => 0; "origin")]
+    #[test_case(7, 7 => 7; "on axis")]
+    #[test_case(8, 7 => 6; "just beyond")]
+    #[test_case(14, 7 => 0; "far edge")]
+    #[test_case(20, 7 => 0; "clamped")]
+    #[test_case(u64::MAX, u64::MAX - 1 => u64::MAX - 2; "huge axis")]
+    #[test_case(u64::MAX, u64::MAX => u64::MAX; "huge on axis")]
+    #[test_case(u64::MAX, u64::MAX / 2 => 0; "huge clamped")]
+    fn reflect_about_axis(value: u64, axis: u64) -> u64 {
+        reflect(value, axis)
+    }
+}
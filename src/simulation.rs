@@ -0,0 +1,79 @@
+//! Implements utilities for deterministic step-by-step simulations.
+use std::hash::Hash;
+
+/// Detects the cycle of a deterministic simulation starting from the `initial` state
+/// where each next state is computed from the previous one by the function `step`,
+/// using Brent's cycle detection algorithm.
+///
+/// Returns `(lead_in, period)` where `lead_in` is the number of steps
+/// before the first state of the cycle is reached, and `period` is the length of the cycle.
+/// Therefore, the state after `n >= lead_in` steps equals the state after
+/// `lead_in + (n - lead_in) % period` steps.
+///
+/// Note that this function never terminates if the simulation never repeats any state.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> (usize, usize)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    // Search for the period: the tortoise teleports to the hare at every power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Search for the lead-in: keep both a period apart until they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut lead_in = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        lead_in += 1;
+    }
+    (lead_in, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(0, 7 => (0, 7); "from start")]
+    #[test_case(5, 7 => (0, 7); "from middle")]
+    #[test_case(0, 1 => (0, 1); "fixed point")]
+    fn modular_increment(initial: u64, modulus: u64) -> (usize, usize) {
+        find_cycle(initial, |&x| (x + 1) % modulus)
+    }
+
+    #[test]
+    fn with_lead_in() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+        let step = |&x: &u64| if x < 6 { x + 1 } else { 3 };
+        assert_eq!(find_cycle(0, step), (3, 4));
+        assert_eq!(find_cycle(4, step), (0, 4));
+    }
+
+    #[test]
+    fn skip_ahead() {
+        let step = |&x: &u64| (x * x + 1) % 255;
+        let (lead_in, period) = find_cycle(3, step);
+        let nth = |n: usize| (0..n).fold(3, |x, _| step(&x));
+        assert!((1..period).all(|k| nth(lead_in) != nth(lead_in + k)));
+        assert_eq!(nth(lead_in), nth(lead_in + period));
+        assert_eq!(nth(1000), nth(lead_in + (1000 - lead_in) % period));
+    }
+}
@@ -1,5 +1,10 @@
 //! Provides Snailfish number parser for Advent of Code Day 18.
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::anyhow;
 use lalrpop_util::lalrpop_mod;
+use lazy_static::lazy_static;
 
 pub use snailfish_syntax::ExprParser as SnailfishParser;
 
@@ -9,9 +14,219 @@ lalrpop_mod!(
     snailfish_syntax
 );
 
+lazy_static! {
+    static ref PARSER: SnailfishParser = SnailfishParser::new();
+}
+
 /// Node in a snailfish number
 #[derive(Debug, Clone)]
 pub enum Node {
     Branch(Box<Node>, Box<Node>),
     Leaf(i64),
 }
+
+impl Node {
+    /// Magnitude of the snailfish number: thrice the magnitude of the left element
+    /// plus twice the magnitude of the right element, or the value itself for a regular number.
+    pub fn magnitude(&self) -> i64 {
+        match self {
+            Node::Branch(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+            Node::Leaf(value) => *value,
+        }
+    }
+
+    /// Obtains the reduced form of the snailfish number by repeatedly exploding
+    /// the leftmost pair nested inside four pairs, or otherwise splitting
+    /// the leftmost regular number of at least 10, until neither action applies.
+    /// This operates directly on the tree, and is equivalent to the reduction
+    /// performed on the stack-oriented serialized representation in day 18.
+    pub fn reduce(mut self) -> Node {
+        while self.explode(0).is_some() || self.split() {}
+        self
+    }
+
+    /// Explodes the leftmost pair of regular numbers nested at `depth` of four or deeper in place,
+    /// and returns the pair of values which have yet to be added to the nearest regular numbers
+    /// on the left and on the right, respectively. Returns `None` if nothing has exploded.
+    fn explode(&mut self, depth: usize) -> Option<(Option<i64>, Option<i64>)> {
+        let (left, right) = match self {
+            Node::Branch(left, right) => (left, right),
+            Node::Leaf(_) => return None,
+        };
+        if depth >= 4 {
+            if let (Node::Leaf(fst), Node::Leaf(snd)) = (left.as_ref(), right.as_ref()) {
+                let carry = (Some(*fst), Some(*snd));
+                *self = Node::Leaf(0);
+                return Some(carry);
+            }
+        }
+        if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+            if let Some(value) = carry_right {
+                right.add_to_leftmost(value);
+            }
+            return Some((carry_left, None));
+        }
+        if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+            if let Some(value) = carry_left {
+                left.add_to_rightmost(value);
+            }
+            return Some((None, carry_right));
+        }
+        None
+    }
+
+    /// Splits the leftmost regular number of at least 10 in place.
+    /// Returns whether the split has occurred.
+    fn split(&mut self) -> bool {
+        match self {
+            Node::Branch(left, right) => left.split() || right.split(),
+            Node::Leaf(value) if *value >= 10 => {
+                let fst = *value / 2;
+                let snd = *value - fst;
+                *self = Node::Branch(Box::new(Node::Leaf(fst)), Box::new(Node::Leaf(snd)));
+                true
+            }
+            Node::Leaf(_) => false,
+        }
+    }
+
+    /// Adds the value to the leftmost regular number.
+    fn add_to_leftmost(&mut self, value: i64) {
+        match self {
+            Node::Branch(left, _) => left.add_to_leftmost(value),
+            Node::Leaf(v) => *v += value,
+        }
+    }
+
+    /// Adds the value to the rightmost regular number.
+    fn add_to_rightmost(&mut self, value: i64) {
+        match self {
+            Node::Branch(_, right) => right.add_to_rightmost(value),
+            Node::Leaf(v) => *v += value,
+        }
+    }
+}
+
+impl FromStr for Node {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PARSER
+            .parse(s)
+            .map_err(|_| anyhow!("cannot parse snailfish number: '{}'", s.escape_default()))
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Node::Branch(left, right) => write!(f, "[{},{}]", left, right),
+            Node::Leaf(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("[[1,2],[[3,4],5]]")]
+    #[test_case("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")]
+    #[test_case("9")]
+    fn round_trip(s: &str) {
+        let node: Node = s.parse().unwrap();
+        assert_eq!(node.to_string(), s);
+    }
+
+    #[test_case("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]")]
+    #[test_case("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]")]
+    #[test_case("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]")]
+    #[test_case(
+        "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]",
+        "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+    )]
+    #[test_case("[[[[0,7],4],[15,[0,13]]],[1,1]]", "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")]
+    fn reduce(s: &str, expected: &str) {
+        let node: Node = s.parse().unwrap();
+        assert_eq!(node.reduce().to_string(), expected);
+    }
+
+    #[test_case("[[1,2],[[3,4],5]]", 143)]
+    #[test_case("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384)]
+    #[test_case("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]", 3488)]
+    fn magnitude(s: &str, expected: i64) {
+        let node: Node = s.parse().unwrap();
+        assert_eq!(node.magnitude(), expected);
+    }
+
+    /// Homework assignment from the example of day 18
+    const HOMEWORK: [&str; 10] = [
+        "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
+        "[[[5,[2,8]],4],[5,[[9,9],0]]]",
+        "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]",
+        "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
+        "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]",
+        "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]",
+        "[[[[5,4],[7,7]],8],[[8,3],8]]",
+        "[[9,3],[[9,9],[6,[4,9]]]]",
+        "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]",
+        "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]",
+    ];
+
+    /// Adds two snailfish numbers without reduction.
+    fn add(left: Node, right: Node) -> Node {
+        Node::Branch(Box::new(left), Box::new(right))
+    }
+
+    #[test]
+    fn homework_sum() {
+        let numbers: Vec<Node> = HOMEWORK.iter().map(|s| s.parse().unwrap()).collect();
+        let result = numbers[1..]
+            .iter()
+            .fold(numbers[0].clone(), |acc, n| add(acc, n.clone()).reduce());
+        assert_eq!(
+            result.to_string(),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+        );
+        assert_eq!(result.magnitude(), 4140);
+    }
+
+    /// Checks invariants of reduced snailfish numbers: no pairs nested inside four pairs
+    /// and no regular numbers of at least 10.
+    fn is_reduced(node: &Node, depth: usize) -> bool {
+        match node {
+            Node::Branch(left, right) => {
+                depth < 4 && is_reduced(left, depth + 1) && is_reduced(right, depth + 1)
+            }
+            Node::Leaf(value) => *value < 10,
+        }
+    }
+
+    #[test]
+    fn homework_pairs_reduced() {
+        let numbers: Vec<Node> = HOMEWORK.iter().map(|s| s.parse().unwrap()).collect();
+        let mut max_magnitude = 0;
+        for (i, fst) in numbers.iter().enumerate() {
+            for (j, snd) in numbers.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let result = add(fst.clone(), snd.clone()).reduce();
+                assert!(is_reduced(&result, 0));
+                assert_eq!(result.clone().reduce().to_string(), result.to_string());
+                max_magnitude = max_magnitude.max(result.magnitude());
+            }
+        }
+        assert_eq!(max_magnitude, 3993);
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            "[1,2".parse::<Node>().unwrap_err().to_string(),
+            "cannot parse snailfish number: '[1,2'"
+        );
+    }
+}
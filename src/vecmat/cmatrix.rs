@@ -1,7 +1,8 @@
 //! Implements basic matrix type and utilities.
 use std::iter::successors;
-use std::ops::{Add, Mul, Neg};
+use std::ops::{Add, Mul, Neg, Sub};
 
+use itertools::{iproduct, izip};
 use num::{One, Zero};
 
 use crate::collect_array::CollectArray;
@@ -15,6 +16,32 @@ impl<T, const ROWS: usize, const COLS: usize> CMatrix<T, ROWS, COLS> {
     pub fn new(elements: [[T; COLS]; ROWS]) -> Self {
         CMatrix(elements)
     }
+
+    /// Applies the function `f` to each element of the matrix
+    /// and produces a new matrix of the same shape.
+    pub fn map<U, F>(self, mut f: F) -> CMatrix<U, ROWS, COLS>
+    where
+        F: FnMut(T) -> U,
+    {
+        CMatrix(self.0.map(|row| row.map(&mut f)))
+    }
+
+    /// Combines two matrices of the same shape element by element using the function `f`.
+    fn zip_with<U, F>(self, other: Self, mut f: F) -> CMatrix<U, ROWS, COLS>
+    where
+        F: FnMut(T, T) -> U,
+    {
+        let result: [[_; COLS]; ROWS] = izip!(self.0.into_iter(), other.0.into_iter())
+            .map(|(lhs_row, rhs_row)| {
+                izip!(lhs_row.into_iter(), rhs_row.into_iter())
+                    .map(|(a, b)| f(a, b))
+                    .collect_exact()
+                    .unwrap()
+            })
+            .collect_exact()
+            .unwrap();
+        CMatrix(result)
+    }
 }
 
 impl<T> CMatrix<T, 2, 2>
@@ -37,7 +64,7 @@ where
     /// A suite of rotational matrices generated by [`CMatrix::rotate_mat`].
     pub fn rotate_suite() -> [Self; 4] {
         successors(Some(Self::identity_mat()), |&m| {
-            Some(CMatrix::rotate_mat() * m)
+            Some(Self::rotate_mat() * m)
         })
         .collect_trunc()
         .unwrap()
@@ -95,31 +122,115 @@ where
     }
 
     /// A suite of rotational matrices generated by [`CMatrix::xyz_rotate_mat`].
+    /// These form the cyclic subgroup of order 3 which cyclically permutes the three axes,
+    /// i.e. rotations around the line `x = y = z`.
     pub fn xyz_rotate_suite() -> [Self; 3] {
         successors(Some(Self::identity_mat()), |&m| {
-            Some(CMatrix::xyz_rotate_mat() * m)
+            Some(Self::xyz_rotate_mat() * m)
         })
         .collect_trunc()
         .unwrap()
     }
 
     /// A suite of rotational matrices generated by [`CMatrix::xy_rotate_mat`].
+    /// These form the cyclic subgroup of order 2 which either keeps the z-axis in place
+    /// or flips it to the opposite direction (while swapping the x-axis with the y-axis).
     pub fn xy_rotate_suite() -> [Self; 2] {
         successors(Some(Self::identity_mat()), |&m| {
-            Some(CMatrix::xy_rotate_mat() * m)
+            Some(Self::xy_rotate_mat() * m)
         })
         .collect_trunc()
         .unwrap()
     }
 
-    /// A suite of rotational matrices generated by [`CMatrix::xy_rotate_mat`].
+    /// A suite of rotational matrices generated by [`CMatrix::z_rotate_mat`].
+    /// These form the cyclic subgroup of order 4 of rotations around the z-axis.
     pub fn z_rotate_suite() -> [Self; 4] {
         successors(Some(Self::identity_mat()), |&m| {
-            Some(CMatrix::z_rotate_mat() * m)
+            Some(Self::z_rotate_mat() * m)
         })
         .collect_trunc()
         .unwrap()
     }
+
+    /// All 24 rotational matrices describing the rotational symmetry
+    /// of an axis-aligned cube centered at the origin.
+    /// Each rotation is obtained by picking which face the x-face is taken to
+    /// (via [`CMatrix::xyz_rotate_suite`] and [`CMatrix::xy_rotate_suite`])
+    /// followed by a rotation around the z-axis (via [`CMatrix::z_rotate_suite`]).
+    pub fn cube_rotations() -> [Self; 24]
+    where
+        T: Add<Output = T> + Mul<Output = T>,
+    {
+        iproduct!(
+            Self::xyz_rotate_suite(),
+            Self::xy_rotate_suite(),
+            Self::z_rotate_suite()
+        )
+        .map(|(a, b, c)| c * b * a)
+        .collect_exact()
+        .unwrap()
+    }
+}
+
+impl<T, const SIZE: usize> CMatrix<T, SIZE, SIZE>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T> + Zero + One + PartialEq,
+{
+    /// Checks whether the matrix is orthogonal,
+    /// i.e. its transpose multiplied by itself yields the identity matrix.
+    pub fn is_orthogonal(&self) -> bool {
+        iproduct!(0..SIZE, 0..SIZE).all(|(i, j)| {
+            let dot = (0..SIZE)
+                .map(|k| self.0[k][i] * self.0[k][j])
+                .fold(T::zero(), |acc, x| acc + x);
+            dot == if i == j { T::one() } else { T::zero() }
+        })
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> CMatrix<T, ROWS, COLS>
+where
+    T: Copy + Add<Output = T> + Zero + Mul<Output = T>,
+{
+    /// Transforms each of the `points` by multiplying this matrix on the left,
+    /// producing the transformed points in the same order.
+    pub fn transform_all(&self, points: &[CVector<T, COLS>]) -> Vec<CVector<T, ROWS>> {
+        points.iter().map(|&p| *self * p).collect()
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> Add for CMatrix<T, ROWS, COLS>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> Sub for CMatrix<T, ROWS, COLS>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> Mul<T> for CMatrix<T, ROWS, COLS>
+where
+    T: Copy + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|a| a * rhs)
+    }
 }
 
 impl<T, const ISIZE: usize, const JSIZE: usize> Mul<CVector<T, JSIZE>> for CMatrix<T, ISIZE, JSIZE>
@@ -165,3 +276,65 @@ where
         CMatrix(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    /// Computes the determinant of a 3x3 matrix by cofactor expansion along the first row.
+    fn determinant(m: &CMatrix<i64, 3, 3>) -> i64 {
+        let [[a, b, c], [d, e, f], [g, h, i]] = m.0;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+
+    #[test]
+    fn test_transform_all() {
+        let rotate = CMatrix::<i64, 2, 2>::rotate_mat();
+        let points = [
+            CVector::new([1, 0]),
+            CVector::new([0, 1]),
+            CVector::new([3, -2]),
+        ];
+        assert_eq!(
+            rotate.transform_all(&points),
+            vec![
+                CVector::new([0, 1]),
+                CVector::new([-1, 0]),
+                CVector::new([2, 3])
+            ]
+        );
+        assert!(rotate.transform_all(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_elementwise_arithmetic() {
+        let a = CMatrix::new([[1_i64, -2, 3], [0, 4, -5]]);
+        assert_eq!(a + a, a * 2);
+        assert_eq!(a - a, CMatrix::new([[0; 3]; 2]));
+        assert_eq!(a * 2 - a, a);
+        assert_eq!(a.map(|x| x.abs()), CMatrix::new([[1, 2, 3], [0, 4, 5]]));
+        assert_eq!(
+            a.map(|x| x > 0),
+            CMatrix::new([[true, false, true], [false, true, false]])
+        );
+    }
+
+    #[test]
+    fn test_cube_rotations() {
+        let rotations = CMatrix::<i64, 3, 3>::cube_rotations();
+        assert!(rotations.iter().tuple_combinations().all(|(a, b)| a != b));
+        assert!(rotations.iter().all(|m| m.is_orthogonal()));
+        assert!(rotations.iter().all(|m| determinant(m) == 1));
+    }
+
+    #[test]
+    fn test_is_orthogonal() {
+        assert!(CMatrix::<i64, 2, 2>::rotate_mat().is_orthogonal());
+        assert!(CMatrix::<i64, 3, 3>::xy_rotate_mat().is_orthogonal());
+        assert!(CMatrix::new([[-1, 0], [0, 1]]).is_orthogonal());
+        assert!(!CMatrix::new([[1, 1], [0, 1]]).is_orthogonal());
+        assert!(!CMatrix::new([[2, 0], [0, 2]]).is_orthogonal());
+    }
+}
@@ -2,9 +2,11 @@
 use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
 
 use itertools::izip;
-use num::{Signed, Zero};
+use nalgebra::{SVector, Scalar};
+use num::{CheckedAdd, CheckedSub, Signed, Zero};
 
 use crate::collect_array::CollectArray;
+use crate::grid::GridPoint;
 use crate::vecmat::CMatrix;
 
 /// Vector whose size is known at compile time.
@@ -17,6 +19,15 @@ impl<T, const SIZE: usize> CVector<T, SIZE> {
         CVector(elements)
     }
 
+    /// Creates a new vector by copying the elements from a slice,
+    /// whose length must be exactly `SIZE`.
+    pub fn from_slice(s: &[T]) -> anyhow::Result<Self>
+    where
+        T: Copy,
+    {
+        Ok(CVector(s.iter().copied().collect_exact()?))
+    }
+
     /// References elements of the vector as a slice.
     pub fn as_slice(&self) -> &[T] {
         self.0.as_slice()
@@ -35,6 +46,16 @@ impl<T, const SIZE: usize> CVector<T, SIZE> {
         self.as_slice().to_vec()
     }
 
+    /// Copies elements of the vector into a constant size array without heap allocation,
+    /// e.g. for use as a sort key. Note that `CVector` itself does not implement [`Ord`],
+    /// but arrays of [`Ord`] elements do so lexicographically (same as [`to_vec`](CVector::to_vec)).
+    pub fn as_key(&self) -> [T; SIZE]
+    where
+        T: Copy,
+    {
+        self.0
+    }
+
     /// Gets a reference to an element from the vector.
     pub fn get(&self, index: usize) -> Option<&T> {
         self.0.get(index)
@@ -57,11 +78,80 @@ impl<T, const SIZE: usize> CVector<T, SIZE> {
     }
 
     /// Computes the maximum norm of the vector.
+    /// Zero-length vectors have the norm of zero, consistent with [`CVector::norm1`].
     pub fn norm_max(&self) -> T
     where
         T: Copy + Signed + Ord,
     {
-        self.0.iter().copied().map(|x| x.abs()).max().unwrap()
+        self.0
+            .iter()
+            .copied()
+            .fold(T::zero(), |acc, x| acc.max(x.abs()))
+    }
+
+    /// Computes the element-wise sign of the vector,
+    /// where each element becomes `-1`, `0`, or `1` (for integers).
+    pub fn signum(&self) -> Self
+    where
+        T: Copy + Signed,
+    {
+        CVector(self.0.map(|x| x.signum()))
+    }
+
+    /// Computes the element-wise absolute value of the vector.
+    pub fn abs(&self) -> Self
+    where
+        T: Copy + Signed,
+    {
+        CVector(self.0.map(|x| x.abs()))
+    }
+
+    /// Computes the element-wise sum of two vectors,
+    /// returning `None` if any of the element additions overflows.
+    pub fn checked_add(&self, other: &Self) -> Option<Self>
+    where
+        T: Copy + CheckedAdd,
+    {
+        let mut result = self.0;
+        for (a, b) in result.iter_mut().zip(other.0.iter()) {
+            *a = a.checked_add(b)?;
+        }
+        Some(CVector(result))
+    }
+
+    /// Computes the element-wise difference of two vectors,
+    /// returning `None` if any of the element subtractions overflows.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self>
+    where
+        T: Copy + CheckedSub,
+    {
+        let mut result = self.0;
+        for (a, b) in result.iter_mut().zip(other.0.iter()) {
+            *a = a.checked_sub(b)?;
+        }
+        Some(CVector(result))
+    }
+
+    /// Computes the point halfway between two vectors in floating-point coordinates.
+    pub fn midpoint(&self, other: &Self) -> CVector<f64, SIZE>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.lerp(other, 0.5)
+    }
+
+    /// Linearly interpolates between two vectors in floating-point coordinates,
+    /// where `t = 0.0` yields `self` and `t = 1.0` yields `other` exactly.
+    pub fn lerp(&self, other: &Self, t: f64) -> CVector<f64, SIZE>
+    where
+        T: Copy + Into<f64>,
+    {
+        let mut result = [0.0; SIZE];
+        for (r, (&a, &b)) in result.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            let (a, b): (f64, f64) = (a.into(), b.into());
+            *r = a * (1.0 - t) + b * t;
+        }
+        CVector(result)
     }
 
     /// An iterator over the index and the value
@@ -174,6 +264,24 @@ where
     }
 }
 
+impl<T, const SIZE: usize> From<CVector<T, SIZE>> for SVector<T, SIZE>
+where
+    T: Scalar,
+{
+    fn from(v: CVector<T, SIZE>) -> Self {
+        SVector::from(v.0)
+    }
+}
+
+impl<T, const SIZE: usize> From<SVector<T, SIZE>> for CVector<T, SIZE>
+where
+    T: Scalar,
+{
+    fn from(v: SVector<T, SIZE>) -> Self {
+        CVector(v.into())
+    }
+}
+
 impl<T> CVector<T, 1> {
     /// First member of the vector
     pub fn x(&self) -> T
@@ -200,6 +308,21 @@ impl<T> CVector<T, 2> {
     {
         self.0[1]
     }
+
+    /// Creates a vector from the grid point tuple-pair.
+    pub fn from_grid_point(point: GridPoint<T>) -> Self {
+        CVector([point.0, point.1])
+    }
+
+    /// Converts the vector into the grid point tuple-pair used by the [`grid`] module.
+    ///
+    /// [`grid`]: crate::grid
+    pub fn as_grid_point(&self) -> GridPoint<T>
+    where
+        T: Copy,
+    {
+        (self.0[0], self.0[1])
+    }
 }
 
 impl<T> CVector<T, 3> {
@@ -261,3 +384,115 @@ impl<T> CVector<T, 4> {
         self.0[3]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn norms() {
+        let v = CVector::new([3, -7, 2]);
+        assert_eq!(v.norm1(), 12);
+        assert_eq!(v.norm_max(), 7);
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let points = vec![
+            CVector::new([1, 5, -2]),
+            CVector::new([-3, 0, 4]),
+            CVector::new([1, -5, 7]),
+            CVector::new([-3, 0, 2]),
+        ];
+        let mut by_key: Vec<CVector<i64, 3>> = points.clone();
+        by_key.sort_by_key(|p| p.as_key());
+        let mut by_vec = points;
+        by_vec.sort_by_key(|p| p.to_vec());
+        assert_eq!(by_key, by_vec);
+        assert_eq!(by_key[0], CVector::new([-3, 0, 2]));
+    }
+
+    #[test]
+    fn from_slice() {
+        let v = CVector::<i64, 3>::from_slice(&[3, -7, 2]).unwrap();
+        assert_eq!(v, CVector::new([3, -7, 2]));
+        assert_eq!(
+            CVector::<i64, 3>::from_slice(&[3, -7])
+                .unwrap_err()
+                .to_string(),
+            "too few items from the iterator (expected 3 but found only 2)"
+        );
+        assert_eq!(
+            CVector::<i64, 2>::from_slice(&[3, -7, 2])
+                .unwrap_err()
+                .to_string(),
+            "too many items from the iterator (expected only 2)"
+        );
+    }
+
+    #[test]
+    fn signum_and_abs() {
+        let v = CVector::new([3_i64, 0, -7]);
+        assert_eq!(v.signum(), CVector::new([1, 0, -1]));
+        assert_eq!(v.abs(), CVector::new([3, 0, 7]));
+        assert_eq!(v.abs().norm1(), v.norm1());
+        assert_eq!(v - v.signum(), CVector::new([2, 0, -6]));
+    }
+
+    #[test]
+    fn grid_point_round_trip() {
+        let v = CVector::from_grid_point((4_usize, 9));
+        assert_eq!((v.x(), v.y()), (4, 9));
+        assert_eq!(v.as_grid_point(), (4, 9));
+        assert_eq!(CVector::from_grid_point(v.as_grid_point()), v);
+    }
+
+    #[test]
+    fn nalgebra_round_trip() {
+        let v = CVector::new([3_i64, -7, 2]);
+        let w = SVector::from(v);
+        assert_eq!(w, nalgebra::vector![3, -7, 2]);
+        assert_eq!(CVector::from(w), v);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let v = CVector::new([i64::MAX - 1, 0, i64::MIN + 1]);
+        let one = CVector::new([1, 1, 1]);
+        assert_eq!(
+            v.checked_add(&one),
+            Some(CVector::new([i64::MAX, 1, i64::MIN + 2]))
+        );
+        assert_eq!(v.checked_add(&one).unwrap().checked_add(&one), None);
+        assert_eq!(
+            v.checked_sub(&one),
+            Some(CVector::new([i64::MAX - 2, -1, i64::MIN]))
+        );
+        assert_eq!(v.checked_sub(&one).unwrap().checked_sub(&one), None);
+    }
+
+    #[test]
+    fn norms_empty() {
+        let v = CVector::<i64, 0>::new([]);
+        assert_eq!(v.norm1(), 0);
+        assert_eq!(v.norm_max(), 0);
+    }
+
+    #[test]
+    fn interpolation() {
+        let v = CVector::new([3_i32, -7, 2]);
+        let w = CVector::new([-1, 5, 2]);
+        assert_eq!(v.lerp(&w, 0.0), CVector::new([3.0, -7.0, 2.0]));
+        assert_eq!(v.lerp(&w, 1.0), CVector::new([-1.0, 5.0, 2.0]));
+        assert_eq!(v.lerp(&w, 0.25), CVector::new([2.0, -4.0, 2.0]));
+        assert_eq!(v.midpoint(&w), CVector::new([1.0, -1.0, 2.0]));
+    }
+
+    #[test]
+    fn interpolation_exact_endpoints() {
+        let v = CVector::new([1e17, -2.5]);
+        let w = CVector::new([3.3, 1e-17]);
+        assert_eq!(v.lerp(&w, 0.0), v);
+        assert_eq!(v.lerp(&w, 1.0), w);
+    }
+}